@@ -1,12 +1,17 @@
 use alloc::string::String;
+use alloc::vec;
 use alloc::vec::Vec;
 
+use lite_json::JsonValue;
+
 use crate::amount_limit::AmountLimit;
-#[cfg(feature = "std")]
+use crate::contract_action::{field, json_string, json_strings, string_arg};
+use crate::Error::JsonParserError;
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct ABI {
     /// interface name
     pub name: String,
@@ -15,3 +20,61 @@ pub struct ABI {
     /// The limits on the amount
     pub amount_limit: Vec<AmountLimit>,
 }
+
+impl ABI {
+    /// ABI as in the contract info of `system.iost/setCode`:
+    /// `{"name":..,"args":[..],"amountLimit":[{"token":..,"val":..}]}`, where empty `args` and
+    /// `amountLimit` are omitted as the chain's own encoding does
+    pub fn to_json(&self) -> JsonValue {
+        let mut abi = vec![("name".chars().collect(), string_arg(&self.name))];
+        if !self.args.is_empty() {
+            abi.push((
+                "args".chars().collect(),
+                JsonValue::Array(self.args.iter().map(string_arg).collect()),
+            ));
+        }
+        if !self.amount_limit.is_empty() {
+            let amount_limit = self
+                .amount_limit
+                .iter()
+                .map(|limit| {
+                    JsonValue::Object(vec![
+                        ("token".chars().collect(), string_arg(&limit.token)),
+                        ("val".chars().collect(), string_arg(&limit.value)),
+                    ])
+                })
+                .collect();
+            abi.push((
+                "amountLimit".chars().collect(),
+                JsonValue::Array(amount_limit),
+            ));
+        }
+        JsonValue::Object(abi)
+    }
+
+    /// Parses an ABI of the contract info, where empty `args` and `amountLimit` may be omitted
+    pub fn from_json(value: &JsonValue) -> crate::Result<Self> {
+        let args = match field(value, "args") {
+            None => Vec::new(),
+            args => json_strings(args)?,
+        };
+        let amount_limit = match field(value, "amountLimit") {
+            None => Vec::new(),
+            Some(JsonValue::Array(limits)) => limits
+                .iter()
+                .map(|limit| {
+                    Ok(AmountLimit::new(
+                        json_string(field(limit, "token"))?.parse()?,
+                        json_string(field(limit, "val"))?.parse()?,
+                    ))
+                })
+                .collect::<crate::Result<_>>()?,
+            Some(_) => return Err(JsonParserError()),
+        };
+        Ok(ABI {
+            name: json_string(field(value, "name"))?,
+            args,
+            amount_limit,
+        })
+    }
+}
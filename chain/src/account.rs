@@ -0,0 +1,45 @@
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{FrozenBalance, Group, Permission, PledgeInfo, RAMInfo, VoteInfo};
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Account {
+    /// account name
+    pub name: String,
+    /// balance of the account
+    pub balance: f64,
+    /// gas information of the account
+    pub gas_info: GasInfo,
+    /// RAM information of the account
+    pub ram_info: RAMInfo,
+    /// permissions of the account, keyed by permission name
+    pub permissions: BTreeMap<String, Permission>,
+    /// permission groups of the account, keyed by group name
+    pub groups: BTreeMap<String, Group>,
+    /// information on frozen balances
+    pub frozen_balances: Vec<FrozenBalance>,
+    /// information on votes
+    pub vote_infos: Vec<VoteInfo>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct GasInfo {
+    /// total amount of gas
+    pub current_total: f64,
+    /// amount of gas which can be transferred
+    pub transferable_gas: f64,
+    /// amount of gas obtained by pledging
+    pub pledge_gas: f64,
+    /// amount of gas increased per second
+    pub increase_speed: f64,
+    /// upper limit of gas obtainable by pledging
+    pub limit: f64,
+    /// information on pledges made by other accounts for this account
+    pub pledged_info: Vec<PledgeInfo>,
+}
@@ -0,0 +1,337 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::{convert::TryFrom, fmt, str::FromStr};
+
+#[cfg(feature = "json")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Error::InvalidAccountName;
+use crate::{NumberBytes, Read, ReadError, SerializeData, Write, WriteError};
+
+/// The minimum character length of an IOST account name.
+pub const ACCOUNT_NAME_LEN_MIN: usize = 5;
+
+/// The maximum character length of an IOST account name.
+pub const ACCOUNT_NAME_LEN_MAX: usize = 11;
+
+/// The maximum character length of a permission name.
+pub const PERMISSION_NAME_LEN_MAX: usize = 31;
+
+fn is_name_char(c: char) -> bool {
+    matches!(c, 'a'..='z' | '0'..='9' | '_')
+}
+
+/// An IOST account name: 5 to 11 characters of `a-z`, `0-9` and `_`.
+///
+/// The default name is empty, as the publisher of a transaction not signed yet.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AccountName(String);
+
+impl AccountName {
+    /// Checks `name` against the IOST naming rules
+    pub fn new<T: Into<String>>(name: T) -> crate::Result<Self> {
+        let name = name.into();
+        match name.chars().count() {
+            ACCOUNT_NAME_LEN_MIN..=ACCOUNT_NAME_LEN_MAX if name.chars().all(is_name_char) => {
+                Ok(AccountName(name))
+            }
+            _ => Err(InvalidAccountName(name)),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether this is the default, empty, name
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Name read from bytes or JSON: a valid name, or the empty name
+    fn new_or_empty(name: String) -> crate::Result<Self> {
+        if name.is_empty() {
+            Ok(AccountName::default())
+        } else {
+            AccountName::new(name)
+        }
+    }
+}
+
+impl FromStr for AccountName {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        AccountName::new(s)
+    }
+}
+
+impl TryFrom<&str> for AccountName {
+    type Error = crate::Error;
+
+    fn try_from(value: &str) -> crate::Result<Self> {
+        AccountName::new(value)
+    }
+}
+
+impl TryFrom<String> for AccountName {
+    type Error = crate::Error;
+
+    fn try_from(value: String) -> crate::Result<Self> {
+        AccountName::new(value)
+    }
+}
+
+impl fmt::Display for AccountName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for AccountName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<AccountName> for String {
+    fn from(name: AccountName) -> Self {
+        name.0
+    }
+}
+
+impl PartialEq<str> for AccountName {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for AccountName {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl NumberBytes for AccountName {
+    fn num_bytes(&self) -> usize {
+        self.0.num_bytes()
+    }
+}
+
+impl Write for AccountName {
+    fn write(&self, bytes: &mut [u8], pos: &mut usize) -> Result<(), WriteError> {
+        self.0.write(bytes, pos)
+    }
+}
+
+impl SerializeData for AccountName {}
+
+impl Read for AccountName {
+    fn read(bytes: &[u8], pos: &mut usize) -> Result<Self, ReadError> {
+        AccountName::new_or_empty(String::read(bytes, pos)?).map_err(|_| ReadError::InvalidValue)
+    }
+}
+
+#[cfg(feature = "json")]
+impl Serialize for AccountName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'de> Deserialize<'de> for AccountName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        AccountName::new_or_empty(name)
+            .map_err(|_| serde::de::Error::custom("invalid IOST account name"))
+    }
+}
+
+/// A permission of an account, written `account@permission`, e.g. `admin@active`. Signers of
+/// transactions are named this way.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AccountPermission {
+    pub account: AccountName,
+    /// 1 to 31 characters of `a-z`, `0-9` and `_`
+    pub permission: String,
+}
+
+impl AccountPermission {
+    pub fn new<T: Into<String>>(account: AccountName, permission: T) -> crate::Result<Self> {
+        let permission = permission.into();
+        if permission.is_empty()
+            || permission.len() > PERMISSION_NAME_LEN_MAX
+            || !permission.chars().all(is_name_char)
+        {
+            return Err(InvalidAccountName(format!("{}@{}", account, permission)));
+        }
+        Ok(AccountPermission {
+            account,
+            permission,
+        })
+    }
+}
+
+impl FromStr for AccountPermission {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let mut parts = s.splitn(2, '@');
+        match (parts.next(), parts.next()) {
+            (Some(account), Some(permission)) => AccountPermission::new(
+                AccountName::new(account).map_err(|_| InvalidAccountName(s.to_string()))?,
+                permission,
+            ),
+            _ => Err(InvalidAccountName(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for AccountPermission {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}@{}", self.account, self.permission)
+    }
+}
+
+impl PartialEq<str> for AccountPermission {
+    fn eq(&self, other: &str) -> bool {
+        let mut parts = other.splitn(2, '@');
+        parts.next() == Some(self.account.as_str()) && parts.next() == Some(&self.permission)
+    }
+}
+
+impl PartialEq<&str> for AccountPermission {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl NumberBytes for AccountPermission {
+    fn num_bytes(&self) -> usize {
+        self.to_string().num_bytes()
+    }
+}
+
+impl Write for AccountPermission {
+    fn write(&self, bytes: &mut [u8], pos: &mut usize) -> Result<(), WriteError> {
+        self.to_string().write(bytes, pos)
+    }
+}
+
+impl SerializeData for AccountPermission {}
+
+impl Read for AccountPermission {
+    fn read(bytes: &[u8], pos: &mut usize) -> Result<Self, ReadError> {
+        String::read(bytes, pos)?
+            .parse()
+            .map_err(|_| ReadError::InvalidValue)
+    }
+}
+
+#[cfg(feature = "json")]
+impl Serialize for AccountPermission {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'de> Deserialize<'de> for AccountPermission {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(|_| serde::de::Error::custom("invalid IOST account permission"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    fn string_bytes(s: &str) -> Vec<u8> {
+        let mut bytes = vec![0u8; s.num_bytes()];
+        s.write(&mut bytes, &mut 0).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn account_name_should_follow_iost_rules() {
+        for name in ["admin", "lispczz3", "vote_iost", "a_1_b_2_c_3"].iter() {
+            assert_eq!(name.parse::<AccountName>().unwrap().to_string(), *name);
+        }
+        for name in [
+            "",
+            "abcd",
+            "Admin",
+            "admin.iost",
+            "eosio.token",
+            "adm-in",
+            "abcdefghijkl",
+            "vote_producer",
+        ]
+        .iter()
+        {
+            assert!(matches!(
+                name.parse::<AccountName>(),
+                Err(InvalidAccountName(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn account_name_should_round_trip() {
+        let name: AccountName = "lispczz3".parse().unwrap();
+        let bytes = name.to_serialize_data().unwrap();
+        assert_eq!(bytes, string_bytes("lispczz3"));
+        assert_eq!(AccountName::read(&bytes, &mut 0).unwrap(), name);
+        let bytes = string_bytes("Bad-Name");
+        assert!(matches!(
+            AccountName::read(&bytes, &mut 0),
+            Err(ReadError::InvalidValue)
+        ));
+
+        assert_eq!(serde_json::to_string(&name).unwrap(), r#""lispczz3""#);
+        assert_eq!(
+            serde_json::from_str::<AccountName>(r#""lispczz3""#).unwrap(),
+            name
+        );
+        assert_eq!(
+            serde_json::from_str::<AccountName>(r#""""#).unwrap(),
+            AccountName::default()
+        );
+        assert!(serde_json::from_str::<AccountName>(r#""eosio.token""#).is_err());
+    }
+
+    #[test]
+    fn account_permission_should_round_trip() {
+        let signer: AccountPermission = "admin@active".parse().unwrap();
+        assert_eq!(signer.account, "admin");
+        assert_eq!(signer.permission, "active");
+        assert_eq!(signer, "admin@active");
+        assert_eq!(signer.to_string(), "admin@active");
+
+        let bytes = signer.to_serialize_data().unwrap();
+        assert_eq!(AccountPermission::read(&bytes, &mut 0).unwrap(), signer);
+        let signers: Vec<AccountPermission> =
+            serde_json::from_str(r#"["admin@active","treasury@owner"]"#).unwrap();
+        assert_eq!(signers, vec!["admin@active", "treasury@owner"]);
+
+        for signer in [
+            "admin",
+            "admin@",
+            "adm@active",
+            "admin@Active",
+            "admin@act@ive",
+        ]
+        .iter()
+        {
+            assert!(matches!(
+                signer.parse::<AccountPermission>(),
+                Err(InvalidAccountName(_))
+            ));
+        }
+    }
+}
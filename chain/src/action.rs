@@ -5,39 +5,36 @@ use core::str::FromStr;
 
 use codec::{Decode, Encode};
 use lite_json::{JsonValue, Serialize};
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 use serde::{
     ser::{Error as SerError, SerializeStruct, Serializer},
     Deserialize, Serialize as SerSerialize,
 };
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 use serde_json::to_string as json_to_string;
 
 use crate::Error::JsonParserError;
-use crate::{AccountName, Error, NumberBytes, Read, ReadError, SerializeData, Write, WriteError};
+use crate::{ContractId, Error, NumberBytes, Read, ReadError, SerializeData, Write, WriteError};
 
 #[derive(Clone, Default, Debug, PartialEq, Encode, Decode, SerializeData)]
 #[iost_root_path = "crate"]
 pub struct IostAction {
-    /// contract name
-    pub contract: Vec<u8>,
+    /// contract id, e.g. token.iost
+    pub contract: ContractId,
     /// function name of the contract
     pub action_name: Vec<u8>,
     /// Specific parameters of the call. Put every parameter in an array, and JSON-serialize this array. It may looks like ["a_string", 13]
     pub data: Vec<u8>,
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 impl serde::ser::Serialize for IostAction {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         let mut state = serializer.serialize_struct("Action", 3)?;
-        state.serialize_field(
-            "contract",
-            String::from_utf8(self.contract.clone()).unwrap().as_str(),
-        )?;
+        state.serialize_field("contract", self.contract.as_str())?;
         state.serialize_field(
             "action_name",
             String::from_utf8(self.action_name.clone())
@@ -52,7 +49,7 @@ impl serde::ser::Serialize for IostAction {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 impl<'de> serde::Deserialize<'de> for IostAction {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -70,14 +67,13 @@ impl<'de> serde::Deserialize<'de> for IostAction {
             where
                 D: serde::de::MapAccess<'de>,
             {
-                let mut contract: Vec<u8> = vec![];
+                let mut contract = ContractId::default();
                 let mut action_name: Vec<u8> = vec![];
                 let mut data: Vec<u8> = vec![];
                 while let Some(field) = map.next_key()? {
                     match field {
                         "contract" => {
-                            let contract_value: String = map.next_value()?;
-                            contract = contract_value.into_bytes();
+                            contract = map.next_value()?;
                         }
                         "action_name" => {
                             let account_name_value: String = map.next_value()?;
@@ -106,12 +102,12 @@ impl<'de> serde::Deserialize<'de> for IostAction {
 }
 
 impl IostAction {
-    pub fn new(contract: String, action_name: String, data: String) -> Self {
-        IostAction {
-            contract: contract.into_bytes(),
+    pub fn new(contract: String, action_name: String, data: String) -> crate::Result<Self> {
+        Ok(IostAction {
+            contract: ContractId::new(contract)?,
             action_name: action_name.into_bytes(),
             data: data.into_bytes(),
-        }
+        })
     }
 
     pub fn from_str<T: AsRef<str>>(
@@ -121,7 +117,7 @@ impl IostAction {
     ) -> crate::Result<Self> {
         // let data = serde_json::to_string(&action_transfer).unwrap();
         Ok(IostAction {
-            contract: contract.as_ref().as_bytes().to_vec(),
+            contract: contract.as_ref().parse()?,
             action_name: action_name.as_ref().as_bytes().to_vec(),
             data: action_transfer.no_std_serialize().as_bytes().to_vec(),
         })
@@ -134,16 +130,21 @@ impl IostAction {
         quantity: T,
         memo: T,
     ) -> crate::Result<IostAction> {
-        let action_transfer = ActionTransfer::from_str(from, to, quantity, memo)?;
-        IostAction::from_str("token.iost", "transfer", action_transfer)
+        crate::token::transfer(
+            "iost",
+            from.as_ref(),
+            to.as_ref(),
+            quantity.as_ref(),
+            memo.as_ref(),
+        )
     }
 
-    pub fn from_shadow_action(shadow_action: Action) -> IostAction {
-        IostAction {
-            contract: shadow_action.contract.into_bytes(),
+    pub fn from_shadow_action(shadow_action: Action) -> crate::Result<IostAction> {
+        Ok(IostAction {
+            contract: shadow_action.contract.parse()?,
             action_name: shadow_action.action_name.into_bytes(),
             data: shadow_action.data.into_bytes(),
-        }
+        })
     }
 
     pub fn no_std_serialize(&self) -> JsonValue {
@@ -174,7 +175,7 @@ impl core::fmt::Display for IostAction {
             "contract: {}\n\
             action_name: {}\n\
             data: {}",
-            String::from_utf8_lossy(self.contract.as_slice()),
+            self.contract,
             String::from_utf8_lossy(self.action_name.as_slice()),
             String::from_utf8_lossy(self.data.as_slice()),
         )
@@ -194,7 +195,7 @@ impl Write for IostAction {
 impl Read for IostAction {
     fn read(bytes: &[u8], pos: &mut usize) -> Result<Self, ReadError> {
         let shadow_action: Action = Action::read(bytes, pos)?;
-        Ok(IostAction::from_shadow_action(shadow_action))
+        IostAction::from_shadow_action(shadow_action).map_err(|_| ReadError::InvalidValue)
     }
 }
 
@@ -205,7 +206,7 @@ impl NumberBytes for IostAction {
     }
 }
 
-#[cfg_attr(feature = "std", derive(SerSerialize, Deserialize))]
+#[cfg_attr(feature = "json", derive(SerSerialize, Deserialize))]
 #[derive(Clone, Debug, Read, Write, NumberBytes, Default, SerializeData)]
 #[iost_root_path = "crate"]
 pub struct ActionTransfer {
@@ -253,7 +254,7 @@ impl ActionTransfer {
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Read, Write, NumberBytes, SerializeData)]
-#[cfg_attr(feature = "std", derive(SerSerialize, Deserialize))]
+#[cfg_attr(feature = "json", derive(SerSerialize, Deserialize))]
 #[iost_root_path = "crate"]
 pub struct Action {
     /// contract name
@@ -269,7 +270,7 @@ pub struct Action {
 impl Action {
     fn from_action(action: &IostAction) -> crate::Result<Action> {
         Ok(Action {
-            contract: String::from_utf8(action.contract.clone()).unwrap(),
+            contract: action.contract.to_string(),
             action_name: String::from_utf8(action.action_name.clone()).unwrap(),
             data: String::from_utf8(action.data.clone()).unwrap(),
         })
@@ -287,7 +288,7 @@ pub trait ToAction: Write + NumberBytes {
         data: String,
     ) -> core::result::Result<IostAction, Error> {
         Ok(IostAction {
-            contract: contract.into_bytes(),
+            contract: contract.parse()?,
             action_name: action_name.into_bytes(),
             data: data.into_bytes(),
         })
@@ -299,7 +300,7 @@ impl FromStr for IostAction {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(IostAction {
-            contract: s.to_string().into_bytes(),
+            contract: s.parse()?,
             action_name: s.to_string().into_bytes(),
             data: s.to_string().into_bytes(),
         })
@@ -308,23 +309,21 @@ impl FromStr for IostAction {
 
 #[cfg(test)]
 mod test {
-    use core::iter::FromIterator;
-
-    use lite_json::parse_json;
-
     use super::*;
+    use crate::token::Transfer;
+    use crate::ContractAction;
 
     #[test]
     fn test_action() {
         let action = IostAction {
-            contract: "iost".to_string().into_bytes(),
+            contract: "token.iost".parse().unwrap(),
             action_name: "iost".to_string().into_bytes(),
             data: "".to_string().into_bytes(),
         };
         let data = action.to_serialize_data().unwrap();
 
         let sa = Action {
-            contract: "iost".to_string(),
+            contract: "token.iost".to_string(),
             action_name: "iost".to_string(),
             data: "".to_string(),
         };
@@ -333,7 +332,7 @@ mod test {
         assert_eq!(data.num_bytes(), sa_data.num_bytes());
         assert_eq!(hex::encode(data), hex::encode(sa_data));
         let other = IostAction {
-            contract: "token.iost".to_string().into_bytes(),
+            contract: "token.iost".parse().unwrap(),
             action_name: "transfer".to_string().into_bytes(),
             data: r#"["iost","admin","lispczz3","100",""]"#.to_string().into_bytes(),
         };
@@ -346,14 +345,14 @@ mod test {
     #[test]
     fn action_serialization() {
         let action = IostAction {
-            contract: "iost".to_string().into_bytes(),
+            contract: "token.iost".parse().unwrap(),
             action_name: "iost".to_string().into_bytes(),
             data: "".to_string().into_bytes(),
         };
         assert_eq!(
             String::from_utf8(action.no_std_serialize().format(4)).unwrap(),
             r#"{
-    "contract": "iost",
+    "contract": "token.iost",
     "action_name": "iost",
     "data": ""
 }"#
@@ -372,32 +371,14 @@ mod test {
         let result_action: Result<IostAction, _> = serde_json::from_str(action_str);
         assert!(result_action.is_ok());
         let action = result_action.unwrap();
-        let data = core::str::from_utf8(&action.data).unwrap();
-
-        let mut action_transfer: ActionTransfer = Default::default();
-        let node_info = parse_json(data).unwrap();
-
-        match node_info {
-            JsonValue::Array(ref json) => {
-                for (i, item) in json.iter().enumerate() {
-                    match item {
-                        JsonValue::String(ref chars) => {
-                            let v = String::from_iter(chars.iter());
-                            match i {
-                                0 => action_transfer.token_type = v,
-                                1 => action_transfer.from = v,
-                                2 => action_transfer.to = v,
-                                3 => action_transfer.amount = v,
-                                4 => action_transfer.memo = v,
-                                _ => (),
-                            }
-                        }
-                        _ => (),
-                    }
-                }
-            }
-            _ => (),
-        }
-        dbg!(action_transfer);
+        let action_transfer = Transfer::from_action(&action).unwrap();
+        assert_eq!(action_transfer.token, "iost");
+        assert_eq!(action_transfer.from, "lispczz5");
+        assert_eq!(action_transfer.to, "bifrost");
+        assert_eq!(action_transfer.amount.to_string(), "1");
+        assert_eq!(
+            action_transfer.memo,
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY@bifrost:IOST"
+        );
     }
 }
@@ -0,0 +1,378 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::{cmp::Ordering, convert::TryFrom, fmt, str::FromStr};
+
+#[cfg(feature = "json")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Error::{
+    FixedParseAbnormalChar, FixedParseAmountFormat, FixedParseDivideByZero, FixedParseDoubleDot,
+    FixedParseOverflow,
+};
+use crate::{NumberBytes, Read, ReadError, SerializeData, Write, WriteError};
+
+/// The maximum number of decimal places of an amount
+pub const AMOUNT_DECIMAL_MAX: u32 = 18;
+
+fn pow10(exp: u32) -> crate::Result<i64> {
+    10i64.checked_pow(exp).ok_or(FixedParseOverflow())
+}
+
+/// A fixed-point token amount, `value / 10^decimal`, as the Fixed type of the chain.
+///
+/// Contracts take amounts as decimal strings, e.g. `"100.5"`, see `FromStr` and `Display`.
+/// Amounts compare by their numeric value, `1.5 == 1.50`. The decimal places never exceed
+/// `AMOUNT_DECIMAL_MAX`, as checked by `new`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Amount {
+    value: i64,
+    decimal: u32,
+}
+
+impl Amount {
+    pub fn new(value: i64, decimal: u32) -> crate::Result<Self> {
+        if decimal > AMOUNT_DECIMAL_MAX {
+            return Err(FixedParseOverflow());
+        }
+        Ok(Amount { value, decimal })
+    }
+
+    /// Parses `amount` with the `decimal` places of a token, truncating extra decimal places as
+    /// the chain does
+    pub fn parse(amount: &str, decimal: u32) -> crate::Result<Self> {
+        amount.parse::<Amount>()?.change_decimal(decimal)
+    }
+
+    /// Value in units of `10^-decimal`
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    /// Number of decimal places
+    pub fn decimal(&self) -> u32 {
+        self.decimal
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.value < 0
+    }
+
+    /// The same amount with `decimal` places, truncated when there are fewer places
+    pub fn change_decimal(&self, decimal: u32) -> crate::Result<Self> {
+        let value = if decimal >= self.decimal {
+            self.value
+                .checked_mul(pow10(decimal - self.decimal)?)
+                .ok_or(FixedParseOverflow())?
+        } else {
+            self.value / pow10(self.decimal - decimal)?
+        };
+        Amount::new(value, decimal)
+    }
+
+    pub fn checked_add(&self, other: &Amount) -> crate::Result<Self> {
+        let decimal = self.decimal.max(other.decimal);
+        let (a, b) = (
+            self.change_decimal(decimal)?,
+            other.change_decimal(decimal)?,
+        );
+        let value = a.value.checked_add(b.value).ok_or(FixedParseOverflow())?;
+        Amount::new(value, decimal)
+    }
+
+    pub fn checked_sub(&self, other: &Amount) -> crate::Result<Self> {
+        let decimal = self.decimal.max(other.decimal);
+        let (a, b) = (
+            self.change_decimal(decimal)?,
+            other.change_decimal(decimal)?,
+        );
+        let value = a.value.checked_sub(b.value).ok_or(FixedParseOverflow())?;
+        Amount::new(value, decimal)
+    }
+
+    /// Product with the decimal places of `self`, truncated
+    pub fn checked_mul(&self, other: &Amount) -> crate::Result<Self> {
+        let value = self.value as i128 * other.value as i128 / pow10(other.decimal)? as i128;
+        let value = i64::try_from(value).map_err(|_| FixedParseOverflow())?;
+        Amount::new(value, self.decimal)
+    }
+
+    /// Quotient with the decimal places of `self`, truncated
+    pub fn checked_div(&self, other: &Amount) -> crate::Result<Self> {
+        if other.is_zero() {
+            return Err(FixedParseDivideByZero());
+        }
+        let value = self.value as i128 * pow10(other.decimal)? as i128 / other.value as i128;
+        let value = i64::try_from(value).map_err(|_| FixedParseOverflow())?;
+        Amount::new(value, self.decimal)
+    }
+
+    /// Value scaled to `AMOUNT_DECIMAL_MAX` places, which can't overflow an `i128`
+    fn scaled(&self) -> i128 {
+        self.value as i128 * 10i128.pow(AMOUNT_DECIMAL_MAX - self.decimal)
+    }
+}
+
+impl FromStr for Amount {
+    type Err = crate::Error;
+
+    /// Parses a decimal string, keeping all its decimal places
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        if digits.is_empty() || digits.starts_with('.') || digits.ends_with('.') {
+            return Err(FixedParseAmountFormat());
+        }
+
+        let mut value: i64 = 0;
+        let mut decimal = 0u32;
+        let mut decimal_start = false;
+        for c in digits.chars() {
+            match c {
+                '.' if decimal_start => return Err(FixedParseDoubleDot()),
+                '.' => decimal_start = true,
+                '0'..='9' => {
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|value| value.checked_add(i64::from(c as u8 - b'0')))
+                        .ok_or(FixedParseOverflow())?;
+                    if decimal_start {
+                        decimal += 1;
+                    }
+                }
+                _ => return Err(FixedParseAbnormalChar()),
+            }
+        }
+        Amount::new(if negative { -value } else { value }, decimal)
+    }
+}
+
+impl TryFrom<&str> for Amount {
+    type Error = crate::Error;
+
+    fn try_from(value: &str) -> crate::Result<Self> {
+        value.parse()
+    }
+}
+
+impl fmt::Display for Amount {
+    /// Writes all the decimal places, e.g. `100.50` for a value of 10050 with 2 decimals
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.is_negative() { "-" } else { "" };
+        let decimal = self.decimal as usize;
+        let digits = format!(
+            "{:0>width$}",
+            (self.value as i128).abs(),
+            width = decimal + 1
+        );
+        if decimal == 0 {
+            write!(f, "{}{}", sign, digits)
+        } else {
+            let (integer, fraction) = digits.split_at(digits.len() - decimal);
+            write!(f, "{}{}.{}", sign, integer, fraction)
+        }
+    }
+}
+
+impl From<Amount> for String {
+    fn from(amount: Amount) -> Self {
+        amount.to_string()
+    }
+}
+
+impl PartialEq for Amount {
+    fn eq(&self, other: &Self) -> bool {
+        self.scaled() == other.scaled()
+    }
+}
+
+impl Eq for Amount {}
+
+impl PartialOrd for Amount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Amount {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.scaled().cmp(&other.scaled())
+    }
+}
+
+impl NumberBytes for Amount {
+    fn num_bytes(&self) -> usize {
+        self.to_string().num_bytes()
+    }
+}
+
+impl Write for Amount {
+    fn write(&self, bytes: &mut [u8], pos: &mut usize) -> Result<(), WriteError> {
+        self.to_string().write(bytes, pos)
+    }
+}
+
+impl SerializeData for Amount {}
+
+impl Read for Amount {
+    fn read(bytes: &[u8], pos: &mut usize) -> Result<Self, ReadError> {
+        String::read(bytes, pos)?
+            .parse()
+            .map_err(|_| ReadError::InvalidValue)
+    }
+}
+
+#[cfg(feature = "json")]
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(|_| serde::de::Error::custom("invalid IOST amount"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Error;
+
+    fn amount(s: &str) -> Amount {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn amount_should_parse_and_format() {
+        assert_eq!(amount("100.5"), Amount::new(1005, 1).unwrap());
+        assert_eq!(amount("100.50").decimal, 2);
+        assert_eq!(amount("100.50").to_string(), "100.50");
+        assert_eq!(amount("0.05").to_string(), "0.05");
+        assert_eq!(amount("-0.05").to_string(), "-0.05");
+        assert_eq!(amount("7").to_string(), "7");
+        assert_eq!(
+            Amount::parse("1.123456789", 8).unwrap().to_string(),
+            "1.12345678"
+        );
+        assert_eq!(Amount::parse("3", 2).unwrap().to_string(), "3.00");
+        assert_eq!(String::from(amount("12.5")), "12.5");
+
+        assert!(matches!(
+            "".parse::<Amount>(),
+            Err(Error::FixedParseAmountFormat())
+        ));
+        assert!(matches!(
+            ".5".parse::<Amount>(),
+            Err(Error::FixedParseAmountFormat())
+        ));
+        assert!(matches!(
+            "5.".parse::<Amount>(),
+            Err(Error::FixedParseAmountFormat())
+        ));
+        assert!(matches!(
+            "-".parse::<Amount>(),
+            Err(Error::FixedParseAmountFormat())
+        ));
+        assert!(matches!(
+            "1.2.3".parse::<Amount>(),
+            Err(Error::FixedParseDoubleDot())
+        ));
+        assert!(matches!(
+            "1,5".parse::<Amount>(),
+            Err(Error::FixedParseAbnormalChar())
+        ));
+        assert!(matches!(
+            "+1".parse::<Amount>(),
+            Err(Error::FixedParseAbnormalChar())
+        ));
+        assert!(matches!(
+            "92233720368547758080".parse::<Amount>(),
+            Err(Error::FixedParseOverflow())
+        ));
+        assert!(matches!(
+            "0.0000000000000000001".parse::<Amount>(),
+            Err(Error::FixedParseOverflow())
+        ));
+    }
+
+    #[test]
+    fn amount_arithmetic_should_be_exact() {
+        assert_eq!(
+            amount("0.1")
+                .checked_add(&amount("0.2"))
+                .unwrap()
+                .to_string(),
+            "0.3"
+        );
+        assert_eq!(
+            amount("100.5")
+                .checked_add(&amount("0.25"))
+                .unwrap()
+                .to_string(),
+            "100.75"
+        );
+        assert_eq!(
+            amount("1").checked_sub(&amount("1.5")).unwrap().to_string(),
+            "-0.5"
+        );
+        assert_eq!(
+            amount("2.50")
+                .checked_mul(&amount("1.5"))
+                .unwrap()
+                .to_string(),
+            "3.75"
+        );
+        assert_eq!(
+            amount("10.00")
+                .checked_div(&amount("3"))
+                .unwrap()
+                .to_string(),
+            "3.33"
+        );
+        assert_eq!(amount("1.5"), amount("1.50"));
+        assert!(amount("1.5") < amount("1.51"));
+        assert!(amount("-2") < amount("0.001"));
+
+        let max = Amount::new(i64::MAX, 0).unwrap();
+        assert!(matches!(
+            max.checked_add(&amount("1")),
+            Err(Error::FixedParseOverflow())
+        ));
+        assert!(matches!(
+            max.checked_mul(&amount("2")),
+            Err(Error::FixedParseOverflow())
+        ));
+        assert!(matches!(
+            max.change_decimal(1),
+            Err(Error::FixedParseOverflow())
+        ));
+        assert!(matches!(
+            amount("1").checked_div(&amount("0.00")),
+            Err(Error::FixedParseDivideByZero())
+        ));
+    }
+
+    #[test]
+    fn amount_should_round_trip() {
+        let amount = amount("100.50");
+        let bytes = amount.to_serialize_data().unwrap();
+        assert_eq!(Amount::read(&bytes, &mut 0).unwrap().to_string(), "100.50");
+
+        assert_eq!(serde_json::to_string(&amount).unwrap(), r#""100.50""#);
+        assert_eq!(
+            serde_json::from_str::<Amount>(r#""100.5""#).unwrap(),
+            amount
+        );
+        assert!(serde_json::from_str::<Amount>(r#""1.2.3""#).is_err());
+    }
+}
@@ -1,23 +1,209 @@
-use alloc::string::String;
+use alloc::collections::btree_map::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
 use alloc::vec;
 use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
 
 use lite_json::JsonValue;
-#[cfg(feature = "std")]
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "json")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{NumberBytes, Read, SerializeData, Write};
+use crate::gas::Pledge;
+use crate::ram::Buy;
+use crate::token::{Transfer, TransferFreeze, TOKEN_CONTRACT};
+use crate::vote_producer::{ApplyRegister, Vote};
+use crate::Error::{AmountLimitExceeded, AmountLimitRequired};
+use crate::{
+    is_call, Amount, ContractAction, IostAction, NumberBytes, Read, ReadError, SerializeData,
+    Write, WriteError,
+};
 
-#[derive(Clone, Default, Debug, NumberBytes, Write, Read, SerializeData)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+/// Token of the amount limit covering all the tokens without a limit of their own
+pub const TOKEN_WILDCARD: &str = "*";
+
+/// Value of an amount limit without limit
+pub const UNLIMITED: &str = "unlimited";
+
+/// Decimal places of the `iost` token
+pub const IOST_DECIMAL: u32 = 8;
+
+/// Decimal places of the `ram` token, in bytes
+pub const RAM_DECIMAL: u32 = 0;
+
+/// Functions of the system contracts spending tokens of the signers other than by a transfer, as
+/// `(contract, function)`
+const SPENDING_CALLS: [(&str, &str); 4] = [
+    (Buy::CONTRACT, Buy::ACTION_NAME),
+    (Pledge::CONTRACT, Pledge::ACTION_NAME),
+    (Vote::CONTRACT, Vote::ACTION_NAME),
+    (ApplyRegister::CONTRACT, ApplyRegister::ACTION_NAME),
+];
+
+/// Whether `action` may spend tokens of the signers other than by a `token.iost` transfer, e.g.
+/// `ram.iost/buy` or any call of a deployed contract, so that its amount limit can't be derived
+/// from the action
+pub fn spends_tokens(action: &IostAction) -> bool {
+    !action.contract.is_system()
+        || SPENDING_CALLS
+            .iter()
+            .any(|(contract, action_name)| is_call(action, contract, action_name))
+}
+
+/// Decimal places of the system tokens, `None` for tokens created by users
+pub fn token_decimal(token: &str) -> Option<u32> {
+    match token {
+        "iost" => Some(IOST_DECIMAL),
+        "ram" => Some(RAM_DECIMAL),
+        _ => None,
+    }
+}
+
+/// Token of an amount limit: a token symbol, or `*` for all the other tokens
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LimitToken {
+    #[default]
+    All,
+    Token(String),
+}
+
+impl FromStr for LimitToken {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        match s {
+            TOKEN_WILDCARD => Ok(LimitToken::All),
+            token => Ok(LimitToken::Token(token.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for LimitToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LimitToken::All => f.write_str(TOKEN_WILDCARD),
+            LimitToken::Token(token) => f.write_str(token),
+        }
+    }
+}
+
+impl PartialEq<str> for LimitToken {
+    fn eq(&self, other: &str) -> bool {
+        match self {
+            LimitToken::All => other == TOKEN_WILDCARD,
+            LimitToken::Token(token) => token == other,
+        }
+    }
+}
+
+impl PartialEq<&str> for LimitToken {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+/// Value of an amount limit
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Limit {
+    #[default]
+    Unlimited,
+    Exact(Amount),
+}
+
+impl Limit {
+    /// Whether spending `amount` stays within the limit
+    pub fn allows(&self, amount: &Amount) -> bool {
+        match self {
+            Limit::Unlimited => true,
+            Limit::Exact(limit) => amount <= limit,
+        }
+    }
+}
+
+impl FromStr for Limit {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        match s {
+            UNLIMITED => Ok(Limit::Unlimited),
+            amount => Ok(Limit::Exact(amount.parse()?)),
+        }
+    }
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Limit::Unlimited => f.write_str(UNLIMITED),
+            Limit::Exact(amount) => amount.fmt(f),
+        }
+    }
+}
+
+impl PartialEq<str> for Limit {
+    fn eq(&self, other: &str) -> bool {
+        matches!(other.parse::<Limit>(), Ok(ref limit) if limit == self)
+    }
+}
+
+impl PartialEq<&str> for Limit {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+macro_rules! impl_string_bytes {
+    ($($t:ty),*) => {$(
+        impl NumberBytes for $t {
+            fn num_bytes(&self) -> usize {
+                self.to_string().num_bytes()
+            }
+        }
+
+        impl Write for $t {
+            fn write(&self, bytes: &mut [u8], pos: &mut usize) -> Result<(), WriteError> {
+                self.to_string().write(bytes, pos)
+            }
+        }
+
+        impl Read for $t {
+            fn read(bytes: &[u8], pos: &mut usize) -> Result<Self, ReadError> {
+                String::read(bytes, pos)?
+                    .parse()
+                    .map_err(|_| ReadError::InvalidValue)
+            }
+        }
+
+        #[cfg(feature = "json")]
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        #[cfg(feature = "json")]
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer)?
+                    .parse()
+                    .map_err(|_| serde::de::Error::custom("invalid IOST amount limit"))
+            }
+        }
+    )*};
+}
+
+impl_string_bytes!(LimitToken, Limit);
+
+#[derive(Clone, Default, Debug, PartialEq, NumberBytes, Write, Read, SerializeData)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 #[iost_root_path = "crate"]
 pub struct AmountLimit {
-    /// token name
-    pub token: String,
+    /// token name, `*` for all the tokens
+    pub token: LimitToken,
     /// corresponding token limit
     // #[cfg(feature = "std")]
     // #[serde(rename = "val")]
-    pub value: String,
+    pub value: Limit,
 }
 //
 // #[cfg(feature = "std")]
@@ -64,22 +250,189 @@ pub struct AmountLimit {
 // }
 
 impl AmountLimit {
-    pub fn new(token: String, value: String) -> Self {
+    pub fn new(token: LimitToken, value: Limit) -> Self {
         AmountLimit { token, value }
     }
 
+    /// No limit on any token, only fit for dry-runs and trusted contracts
+    pub fn unlimited() -> Self {
+        AmountLimit::new(LimitToken::All, Limit::Unlimited)
+    }
+
+    /// Limit of `amount` on `token`, with the decimal places of the token when it is a system
+    /// token
+    pub fn exact<T: Into<String>>(token: T, amount: Amount) -> crate::Result<Self> {
+        let token = token.into();
+        let amount = match token_decimal(&token) {
+            Some(decimal) => amount.change_decimal(decimal)?,
+            None => amount,
+        };
+        Ok(AmountLimit::new(token.parse()?, Limit::Exact(amount)))
+    }
+
+    /// Tight limits covering the `token.iost` transfers of `actions` from the accounts `payers`,
+    /// or from any account if `payers` is empty. Fails with `AmountLimitRequired` for an action
+    /// spending tokens otherwise, see `spends_tokens`: its limits have to be stated, e.g. from a
+    /// `TxEstimate`.
+    pub fn from_actions(actions: &[IostAction], payers: &[&str]) -> crate::Result<Vec<Self>> {
+        if let Some(action) = actions.iter().find(|action| spends_tokens(action)) {
+            return Err(AmountLimitRequired(format!(
+                "{}/{}",
+                action.contract,
+                String::from_utf8_lossy(&action.action_name)
+            )));
+        }
+        transfers_by_token(actions, payers)?
+            .into_iter()
+            .map(|(token, amount)| AmountLimit::exact(token, amount))
+            .collect()
+    }
+
+    /// Checks that `limits` cover the `token.iost` transfers of `actions` from the accounts
+    /// `payers`, or from any account if `payers` is empty
+    pub fn check(
+        limits: &[AmountLimit],
+        actions: &[IostAction],
+        payers: &[&str],
+    ) -> crate::Result<()> {
+        for (token, amount) in transfers_by_token(actions, payers)? {
+            let limit = limits
+                .iter()
+                .find(|limit| limit.token == token.as_str())
+                .or_else(|| limits.iter().find(|limit| limit.token == LimitToken::All));
+            match limit {
+                Some(limit) if limit.value.allows(&amount) => {}
+                _ => return Err(AmountLimitExceeded(token)),
+            }
+        }
+        Ok(())
+    }
+
     pub fn no_std_serialize(&self) -> JsonValue {
         let object = JsonValue::Object(vec![
             (
                 "token".chars().collect::<Vec<_>>(),
-                JsonValue::String(self.token.chars().collect()),
+                JsonValue::String(self.token.to_string().chars().collect()),
             ),
             (
                 "value".chars().collect::<Vec<_>>(),
-                JsonValue::String(self.value.chars().collect()),
+                JsonValue::String(self.value.to_string().chars().collect()),
             ),
         ]);
         object
         // String::from_utf8(object.format(4)).unwrap()
     }
 }
+
+fn transfers_by_token(
+    actions: &[IostAction],
+    payers: &[&str],
+) -> crate::Result<BTreeMap<String, Amount>> {
+    let mut transfers: BTreeMap<String, Amount> = BTreeMap::new();
+    for action in actions {
+        let (token, from, amount) = if is_call(action, TOKEN_CONTRACT, Transfer::ACTION_NAME) {
+            let transfer = Transfer::from_action(action)?;
+            (transfer.token, transfer.from, transfer.amount)
+        } else if is_call(action, TOKEN_CONTRACT, TransferFreeze::ACTION_NAME) {
+            let transfer = TransferFreeze::from_action(action)?;
+            (transfer.token, transfer.from, transfer.amount)
+        } else {
+            continue;
+        };
+        if payers.is_empty() || payers.contains(&from.as_str()) {
+            let total = transfers.entry(token).or_default();
+            *total = total.checked_add(&amount)?;
+        }
+    }
+    Ok(transfers)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn transfer(from: &str, amount: &str) -> IostAction {
+        IostAction::transfer(from, "lispczz3", amount, "").unwrap()
+    }
+
+    #[test]
+    fn amount_limit_should_round_trip() {
+        let limits: Vec<AmountLimit> = serde_json::from_str(
+            r#"[{"token":"*","value":"unlimited"},{"token":"iost","value":"10.5"}]"#,
+        )
+        .unwrap();
+        assert_eq!(limits[0], AmountLimit::unlimited());
+        assert_eq!(limits[1].token, "iost");
+        assert_eq!(limits[1].value, Limit::Exact("10.5".parse().unwrap()));
+        assert_eq!(
+            serde_json::to_string(&limits[1]).unwrap(),
+            r#"{"token":"iost","value":"10.5"}"#
+        );
+        assert!(
+            serde_json::from_str::<AmountLimit>(r#"{"token":"iost","value":"1.2.3"}"#).is_err()
+        );
+
+        let bytes = limits[1].to_serialize_data().unwrap();
+        assert_eq!(AmountLimit::read(&bytes, &mut 0).unwrap(), limits[1]);
+    }
+
+    #[test]
+    fn amount_limit_should_cover_transfers() {
+        let actions = vec![
+            transfer("admin", "100.5"),
+            transfer("admin", "0.25"),
+            transfer("treasury", "7"),
+        ];
+        let limits = AmountLimit::from_actions(&actions, &["admin"]).unwrap();
+        assert_eq!(limits.len(), 1);
+        assert_eq!(limits[0].token, "iost");
+        assert_eq!(limits[0].value.to_string(), "100.75000000");
+        assert!(AmountLimit::check(&limits, &actions, &["admin"]).is_ok());
+        assert!(matches!(
+            AmountLimit::check(&limits, &actions, &[]),
+            Err(AmountLimitExceeded(ref token)) if token == "iost"
+        ));
+
+        let limits = AmountLimit::from_actions(&actions, &[]).unwrap();
+        assert_eq!(limits[0].value, "107.75");
+        assert!(AmountLimit::check(&limits, &actions, &[]).is_ok());
+
+        // the wildcard covers tokens without a limit of their own
+        let wildcard = vec![AmountLimit::new(
+            LimitToken::All,
+            Limit::Exact("200".parse().unwrap()),
+        )];
+        assert!(AmountLimit::check(&wildcard, &actions, &[]).is_ok());
+        let mut limits = wildcard.clone();
+        limits.push(AmountLimit::exact("iost", "100".parse().unwrap()).unwrap());
+        assert!(AmountLimit::check(&limits, &actions, &[]).is_err());
+        assert!(AmountLimit::check(&[], &actions, &[]).is_err());
+        assert!(AmountLimit::check(&[AmountLimit::unlimited()], &actions, &[]).is_ok());
+    }
+
+    #[test]
+    fn amount_limit_should_be_required_for_spending_calls() {
+        let pledge = Pledge {
+            pledger: "admin".parse().unwrap(),
+            to: "admin".parse().unwrap(),
+            amount: "10".parse().unwrap(),
+        }
+        .to_action()
+        .unwrap();
+        assert!(spends_tokens(&pledge));
+        assert!(!spends_tokens(&transfer("admin", "1")));
+        assert!(matches!(
+            AmountLimit::from_actions(&[transfer("admin", "1"), pledge], &[]),
+            Err(AmountLimitRequired(ref call)) if call == "gas.iost/pledge"
+        ));
+
+        let paid_call = IostAction {
+            contract: "ContractCvwZq8SJPqygvSu5SSVNsuWqMGTDyKBPr6Gd74crJiiR"
+                .parse()
+                .unwrap(),
+            action_name: b"buy".to_vec(),
+            data: b"[]".to_vec(),
+        };
+        assert!(spends_tokens(&paid_call));
+    }
+}
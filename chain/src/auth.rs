@@ -0,0 +1,633 @@
+//! Typed actions of the `auth.iost` contract, which manages accounts, their permissions and
+//! permission groups
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
+
+use keys::public::PublicKey;
+use lite_json::JsonValue;
+
+use crate::contract_action::{
+    arg_i64, arg_key, arg_parse, arg_string, number_arg, parse_args, string_arg,
+};
+use crate::gas::Pledge;
+use crate::ram::Buy;
+use crate::Error::{Keys, UnexpectedAction};
+use crate::{AccountName, AccountPermission, Amount, AmountLimit, ContractAction, IostAction, Tx};
+
+/// Id of the account contract
+pub const AUTH_CONTRACT: &str = "auth.iost";
+
+/// Who a permission or group is granted to: a key, or a permission of another account
+#[derive(Clone, Debug, PartialEq)]
+pub enum Grantee {
+    Key(PublicKey),
+    Permission(AccountPermission),
+}
+
+impl FromStr for Grantee {
+    type Err = crate::Error;
+
+    /// Parses a base58 public key, or `account@permission`
+    fn from_str(s: &str) -> crate::Result<Self> {
+        if s.contains('@') {
+            Ok(Grantee::Permission(s.parse()?))
+        } else {
+            Ok(Grantee::Key(s.parse().map_err(Keys)?))
+        }
+    }
+}
+
+impl fmt::Display for Grantee {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Grantee::Key(key) => key.fmt(f),
+            Grantee::Permission(permission) => permission.fmt(f),
+        }
+    }
+}
+
+/// Creation of the account `name` with the keys of its `owner` and `active` permissions
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignUp {
+    pub name: AccountName,
+    pub owner: PublicKey,
+    pub active: PublicKey,
+}
+
+impl ContractAction for SignUp {
+    const CONTRACT: &'static str = AUTH_CONTRACT;
+    const ACTION_NAME: &'static str = "signUp";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.name),
+            string_arg(&self.owner),
+            string_arg(&self.active),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(SignUp {
+            name: arg_parse(args, 0)?,
+            owner: arg_key(args, 1)?,
+            active: arg_key(args, 2)?,
+        })
+    }
+}
+
+/// Adds the permission `permission` to `account`, satisfied once the weights of its signers
+/// reach `threshold`
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddPermission {
+    pub account: AccountName,
+    pub permission: String,
+    pub threshold: i64,
+}
+
+impl ContractAction for AddPermission {
+    const CONTRACT: &'static str = AUTH_CONTRACT;
+    const ACTION_NAME: &'static str = "addPermission";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.account),
+            string_arg(&self.permission),
+            number_arg(self.threshold),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(AddPermission {
+            account: arg_parse(args, 0)?,
+            permission: arg_string(args, 1)?,
+            threshold: arg_i64(args, 2)?,
+        })
+    }
+}
+
+/// Drops the permission `permission` of `account`
+#[derive(Clone, Debug, PartialEq)]
+pub struct DropPermission {
+    pub account: AccountName,
+    pub permission: String,
+}
+
+impl ContractAction for DropPermission {
+    const CONTRACT: &'static str = AUTH_CONTRACT;
+    const ACTION_NAME: &'static str = "dropPermission";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![string_arg(&self.account), string_arg(&self.permission)]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(DropPermission {
+            account: arg_parse(args, 0)?,
+            permission: arg_string(args, 1)?,
+        })
+    }
+}
+
+/// Grants the permission `permission` of `account` to `grantee`, with `weight`
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssignPermission {
+    pub account: AccountName,
+    pub permission: String,
+    pub grantee: Grantee,
+    pub weight: i64,
+}
+
+impl ContractAction for AssignPermission {
+    const CONTRACT: &'static str = AUTH_CONTRACT;
+    const ACTION_NAME: &'static str = "assignPermission";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.account),
+            string_arg(&self.permission),
+            string_arg(&self.grantee),
+            number_arg(self.weight),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(AssignPermission {
+            account: arg_parse(args, 0)?,
+            permission: arg_string(args, 1)?,
+            grantee: arg_parse(args, 2)?,
+            weight: arg_i64(args, 3)?,
+        })
+    }
+}
+
+/// Revokes the permission `permission` of `account` from `grantee`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RevokePermission {
+    pub account: AccountName,
+    pub permission: String,
+    pub grantee: Grantee,
+}
+
+impl ContractAction for RevokePermission {
+    const CONTRACT: &'static str = AUTH_CONTRACT;
+    const ACTION_NAME: &'static str = "revokePermission";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.account),
+            string_arg(&self.permission),
+            string_arg(&self.grantee),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(RevokePermission {
+            account: arg_parse(args, 0)?,
+            permission: arg_string(args, 1)?,
+            grantee: arg_parse(args, 2)?,
+        })
+    }
+}
+
+/// Adds the permission group `group` to `account`
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddGroup {
+    pub account: AccountName,
+    pub group: String,
+}
+
+impl ContractAction for AddGroup {
+    const CONTRACT: &'static str = AUTH_CONTRACT;
+    const ACTION_NAME: &'static str = "addGroup";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![string_arg(&self.account), string_arg(&self.group)]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(AddGroup {
+            account: arg_parse(args, 0)?,
+            group: arg_string(args, 1)?,
+        })
+    }
+}
+
+/// Drops the permission group `group` of `account`
+#[derive(Clone, Debug, PartialEq)]
+pub struct DropGroup {
+    pub account: AccountName,
+    pub group: String,
+}
+
+impl ContractAction for DropGroup {
+    const CONTRACT: &'static str = AUTH_CONTRACT;
+    const ACTION_NAME: &'static str = "dropGroup";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![string_arg(&self.account), string_arg(&self.group)]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(DropGroup {
+            account: arg_parse(args, 0)?,
+            group: arg_string(args, 1)?,
+        })
+    }
+}
+
+/// Adds `grantee` to the permission group `group` of `account`, with `weight`
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssignGroup {
+    pub account: AccountName,
+    pub group: String,
+    pub grantee: Grantee,
+    pub weight: i64,
+}
+
+impl ContractAction for AssignGroup {
+    const CONTRACT: &'static str = AUTH_CONTRACT;
+    const ACTION_NAME: &'static str = "assignGroup";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.account),
+            string_arg(&self.group),
+            string_arg(&self.grantee),
+            number_arg(self.weight),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(AssignGroup {
+            account: arg_parse(args, 0)?,
+            group: arg_string(args, 1)?,
+            grantee: arg_parse(args, 2)?,
+            weight: arg_i64(args, 3)?,
+        })
+    }
+}
+
+/// Removes `grantee` from the permission group `group` of `account`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RevokeGroup {
+    pub account: AccountName,
+    pub group: String,
+    pub grantee: Grantee,
+}
+
+impl ContractAction for RevokeGroup {
+    const CONTRACT: &'static str = AUTH_CONTRACT;
+    const ACTION_NAME: &'static str = "revokeGroup";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.account),
+            string_arg(&self.group),
+            string_arg(&self.grantee),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(RevokeGroup {
+            account: arg_parse(args, 0)?,
+            group: arg_string(args, 1)?,
+            grantee: arg_parse(args, 2)?,
+        })
+    }
+}
+
+/// Gives the permission `permission` of `account` to the members of its group `group`
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssignPermissionToGroup {
+    pub account: AccountName,
+    pub permission: String,
+    pub group: String,
+}
+
+impl ContractAction for AssignPermissionToGroup {
+    const CONTRACT: &'static str = AUTH_CONTRACT;
+    const ACTION_NAME: &'static str = "assignPermissionToGroup";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.account),
+            string_arg(&self.permission),
+            string_arg(&self.group),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(AssignPermissionToGroup {
+            account: arg_parse(args, 0)?,
+            permission: arg_string(args, 1)?,
+            group: arg_string(args, 2)?,
+        })
+    }
+}
+
+/// Takes the permission `permission` of `account` back from its group `group`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RevokePermissionInGroup {
+    pub account: AccountName,
+    pub permission: String,
+    pub group: String,
+}
+
+impl ContractAction for RevokePermissionInGroup {
+    const CONTRACT: &'static str = AUTH_CONTRACT;
+    const ACTION_NAME: &'static str = "revokePermissionInGroup";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.account),
+            string_arg(&self.permission),
+            string_arg(&self.group),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(RevokePermissionInGroup {
+            account: arg_parse(args, 0)?,
+            permission: arg_string(args, 1)?,
+            group: arg_string(args, 2)?,
+        })
+    }
+}
+
+/// Any decoded action of the account contract
+#[derive(Clone, Debug, PartialEq)]
+pub enum AuthAction {
+    SignUp(SignUp),
+    AddPermission(AddPermission),
+    DropPermission(DropPermission),
+    AssignPermission(AssignPermission),
+    RevokePermission(RevokePermission),
+    AddGroup(AddGroup),
+    DropGroup(DropGroup),
+    AssignGroup(AssignGroup),
+    RevokeGroup(RevokeGroup),
+    AssignPermissionToGroup(AssignPermissionToGroup),
+    RevokePermissionInGroup(RevokePermissionInGroup),
+}
+
+impl AuthAction {
+    pub fn from_action(action: &IostAction) -> crate::Result<Self> {
+        let action_name = String::from_utf8_lossy(&action.action_name);
+        let unexpected = || UnexpectedAction(format!("{}/{}", action.contract, action_name));
+        if action.contract != AUTH_CONTRACT {
+            return Err(unexpected());
+        }
+        let args = parse_args(&action.data)?;
+        Ok(match action_name.as_ref() {
+            SignUp::ACTION_NAME => AuthAction::SignUp(SignUp::from_args(&args)?),
+            AddPermission::ACTION_NAME => {
+                AuthAction::AddPermission(AddPermission::from_args(&args)?)
+            }
+            DropPermission::ACTION_NAME => {
+                AuthAction::DropPermission(DropPermission::from_args(&args)?)
+            }
+            AssignPermission::ACTION_NAME => {
+                AuthAction::AssignPermission(AssignPermission::from_args(&args)?)
+            }
+            RevokePermission::ACTION_NAME => {
+                AuthAction::RevokePermission(RevokePermission::from_args(&args)?)
+            }
+            AddGroup::ACTION_NAME => AuthAction::AddGroup(AddGroup::from_args(&args)?),
+            DropGroup::ACTION_NAME => AuthAction::DropGroup(DropGroup::from_args(&args)?),
+            AssignGroup::ACTION_NAME => AuthAction::AssignGroup(AssignGroup::from_args(&args)?),
+            RevokeGroup::ACTION_NAME => AuthAction::RevokeGroup(RevokeGroup::from_args(&args)?),
+            AssignPermissionToGroup::ACTION_NAME => {
+                AuthAction::AssignPermissionToGroup(AssignPermissionToGroup::from_args(&args)?)
+            }
+            RevokePermissionInGroup::ACTION_NAME => {
+                AuthAction::RevokePermissionInGroup(RevokePermissionInGroup::from_args(&args)?)
+            }
+            _ => return Err(unexpected()),
+        })
+    }
+}
+
+/// Creation of an account paid by `creator`, as done by `iwallet account create`: the sign up,
+/// then the RAM bought, the IOST pledged for gas and the IOST transferred to the new account
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreateAccount {
+    pub creator: AccountName,
+    pub name: AccountName,
+    pub owner: PublicKey,
+    pub active: PublicKey,
+    /// RAM bought for the new account, in bytes
+    pub initial_ram: i64,
+    /// IOST pledged for the gas of the new account
+    pub initial_gas_pledge: Amount,
+    /// IOST transferred to the new account
+    pub initial_balance: Amount,
+}
+
+impl CreateAccount {
+    pub fn to_actions(&self) -> crate::Result<Vec<IostAction>> {
+        let mut actions = vec![SignUp {
+            name: self.name.clone(),
+            owner: self.owner,
+            active: self.active,
+        }
+        .to_action()?];
+        if self.initial_ram > 0 {
+            actions.push(
+                Buy {
+                    payer: self.creator.clone(),
+                    account: self.name.clone(),
+                    bytes: self.initial_ram,
+                }
+                .to_action()?,
+            );
+        }
+        if !self.initial_gas_pledge.is_zero() {
+            actions.push(
+                Pledge {
+                    pledger: self.creator.clone(),
+                    to: self.name.clone(),
+                    amount: self.initial_gas_pledge,
+                }
+                .to_action()?,
+            );
+        }
+        if !self.initial_balance.is_zero() {
+            actions.push(crate::token::transfer(
+                "iost",
+                self.creator.as_str(),
+                self.name.as_str(),
+                &self.initial_balance.to_string(),
+                "",
+            )?);
+        }
+        Ok(actions)
+    }
+
+    /// Transaction creating the account, with an amount limit of IOST covering the pledge, the
+    /// transfer and at most `max_ram_cost` for the RAM, see `RamMarket::buy_cost`
+    pub fn to_tx(
+        &self,
+        time: i64,
+        expiration: i64,
+        chain_id: u32,
+        max_ram_cost: Amount,
+    ) -> crate::Result<Tx> {
+        let spent = self
+            .initial_gas_pledge
+            .checked_add(&self.initial_balance)?
+            .checked_add(&max_ram_cost)?;
+        Tx::with_amount_limit(
+            time,
+            expiration,
+            chain_id,
+            self.to_actions()?,
+            vec![AmountLimit::exact("iost", spent)?],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use keys::algorithm;
+    use keys::keypair::KeyPair;
+
+    const OWNER: &str = "Gcv8c2tH8qZrUYnKdEEdTtASsxivic2834MQW6mgxqto";
+
+    fn active() -> PublicKey {
+        *KeyPair::from_secret_base58(
+            algorithm::SECP256K1,
+            "xjggJ3TrLXz7qEwrGG3Rc4Fz59imjixhXpViq9W7Ncx",
+        )
+        .unwrap()
+        .public()
+    }
+
+    fn data(action: &IostAction) -> &str {
+        core::str::from_utf8(&action.data).unwrap()
+    }
+
+    #[test]
+    fn auth_actions_should_be_encoded_as_abi() {
+        let sign_up = SignUp {
+            name: "lispczz3".parse().unwrap(),
+            owner: OWNER.parse().unwrap(),
+            active: active(),
+        };
+        let action = sign_up.to_action().unwrap();
+        assert_eq!(action.contract, AUTH_CONTRACT);
+        assert_eq!(action.action_name, b"signUp");
+        assert_eq!(
+            data(&action),
+            format!(r#"["lispczz3","{}","{}"]"#, OWNER, active())
+        );
+
+        let assign = AssignPermission {
+            account: "lispczz3".parse().unwrap(),
+            permission: "transfer".to_string(),
+            grantee: "admin@active".parse().unwrap(),
+            weight: 1,
+        };
+        assert_eq!(
+            data(&assign.to_action().unwrap()),
+            r#"["lispczz3","transfer","admin@active",1]"#
+        );
+        let add = AddPermission {
+            account: "lispczz3".parse().unwrap(),
+            permission: "transfer".to_string(),
+            threshold: 2,
+        };
+        assert_eq!(
+            data(&add.to_action().unwrap()),
+            r#"["lispczz3","transfer",2]"#
+        );
+
+        for action in [
+            sign_up.to_action().unwrap(),
+            assign.to_action().unwrap(),
+            AssignGroup {
+                account: "lispczz3".parse().unwrap(),
+                group: "ops".to_string(),
+                grantee: Grantee::Key(OWNER.parse().unwrap()),
+                weight: 100,
+            }
+            .to_action()
+            .unwrap(),
+        ]
+        .iter()
+        {
+            let decoded = AuthAction::from_action(action).unwrap();
+            let encoded = match decoded {
+                AuthAction::SignUp(a) => a.to_action(),
+                AuthAction::AssignPermission(a) => a.to_action(),
+                AuthAction::AssignGroup(a) => a.to_action(),
+                _ => panic!("unexpected action"),
+            };
+            assert_eq!(encoded.unwrap(), *action);
+        }
+
+        assert!("admin".parse::<Grantee>().is_err());
+        assert!(matches!(
+            SignUp::from_action(&assign.to_action().unwrap()),
+            Err(UnexpectedAction(_))
+        ));
+    }
+
+    #[test]
+    fn create_account_should_bundle_actions() {
+        let create = CreateAccount {
+            creator: "admin".parse().unwrap(),
+            name: "lispczz3".parse().unwrap(),
+            owner: OWNER.parse().unwrap(),
+            active: active(),
+            initial_ram: 1024,
+            initial_gas_pledge: "10".parse().unwrap(),
+            initial_balance: "0.5".parse().unwrap(),
+        };
+        let tx = create
+            .to_tx(
+                1544709662543340000,
+                1544709692318715000,
+                1024,
+                "3".parse().unwrap(),
+            )
+            .unwrap();
+        let calls: Vec<String> = tx
+            .actions
+            .iter()
+            .map(|action| {
+                format!(
+                    "{}/{} {}",
+                    action.contract,
+                    String::from_utf8_lossy(&action.action_name),
+                    data(action)
+                )
+            })
+            .collect();
+        assert_eq!(
+            calls[1..],
+            [
+                r#"ram.iost/buy ["admin","lispczz3",1024]"#,
+                r#"gas.iost/pledge ["admin","lispczz3","10"]"#,
+                r#"token.iost/transfer ["iost","admin","lispczz3","0.5",""]"#,
+            ]
+        );
+        assert!(calls[0].starts_with("auth.iost/signUp"));
+        assert_eq!(tx.amount_limit.len(), 1);
+        assert_eq!(tx.amount_limit[0].token, "iost");
+        assert_eq!(tx.amount_limit[0].value, "13.5");
+
+        let create = CreateAccount {
+            initial_ram: 0,
+            initial_gas_pledge: Amount::default(),
+            initial_balance: Amount::default(),
+            ..create
+        };
+        assert_eq!(create.to_actions().unwrap().len(), 1);
+    }
+}
@@ -1,63 +1,44 @@
-// #[cfg(feature = "std")]
+use alloc::string::String;
+use alloc::vec::Vec;
 
-// use base64;
-// #[cfg(feature = "std")]
-// use serde::{Deserialize, Serialize};
-// use sha3::{Digest, Sha3_256};
-//
-// use alloc::collections::BTreeMap;
-// use alloc::format;
-// use alloc::string::String;
-// use alloc::vec::Vec;
-// use keys::algorithm;
-//
-// use crate::Error::IOSTBlockVerifyError;
-// use crate::Result;
-// use crate::SerializeData;
-// use crate::spv::{Head, Sign, Tx};
-// use crate::TxReceipt;
-//
-// #[derive(Debug)]
-// #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-// pub struct Block {
-//     /// block hash
-//     pub head: Head,
-//     /// block version number
-//     pub sign: Sign,
-//     /// public key of the block producer
-//     pub receipts: Vec<TxReceipt>,
-//     /// time of block production
-//     pub txs: Vec<Tx>,
-//     /// transaction number in the block
-//     #[cfg(feature = "std")]
-//     #[serde(rename = "txHashes")]
-//     pub tx_hashes: Vec<String>,
-//
-//     #[cfg(feature = "std")]
-//     #[serde(rename = "receiptHashes")]
-//     pub receipt_hashes: Vec<String>,
-//
-//     #[cfg(feature = "std")]
-//     #[serde(rename = "blockType")]
-//     pub block_type: String,
-// }
-//
-// impl Block {
-//     pub(crate) fn verify_self(&self) -> Result<()> {
-//         let ed25519 = algorithm::new(algorithm::ED25519);
-//         let sign = base64::decode(self.sign.sig.as_str()).unwrap();
-//         // #[cfg(feature = "std")]
-//         // let pub_key = self.head.witness.from_base58().unwrap();
-//         let pub_key = vec![];
-//         let hash = self.head.hash();
-//
-//         if !ed25519.verify(hash.as_slice(), pub_key.as_slice(), sign.as_slice()) {
-//             return Err(IOSTBlockVerifyError(format!("The signature of block {} is wrong", "EsoovjyX7ViMkbSs2y8gniJ7WQW33m8jVM8zvuZMKRRU")));
-//         }
-//
-//         if self.txs.len() != self.receipts.len() {
-//             return Err(IOSTBlockVerifyError(format!("Tx len {} unmatch receipt len {}", self.txs.len(), self.receipts.len())));
-//         }
-//         Ok(())
-//     }
-// }
\ No newline at end of file
+use crate::{Info, Status, Transaction};
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct BlockResponse {
+    /// PENDING - in cache; IRREVERSIBLE - irreversible
+    pub status: Status,
+    /// the block
+    pub block: Block,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Block {
+    /// block hash
+    pub hash: String,
+    /// block version number
+    pub version: String,
+    /// hash of the parent block
+    pub parent_hash: String,
+    /// hash of the transaction merkle tree
+    pub tx_merkle_hash: String,
+    /// hash of the receipt merkle tree
+    pub tx_receipt_merkle_hash: String,
+    /// block number
+    pub number: String,
+    /// public key of the block producer
+    pub witness: String,
+    /// time of block production
+    pub time: String,
+    /// GAS consumption of the block
+    pub gas_usage: f64,
+    /// transaction number in the block
+    pub tx_count: String,
+    /// reserved field
+    pub info: Info,
+    /// transactions in the block, only filled when the block is requested complete
+    pub transactions: Vec<Transaction>,
+}
@@ -0,0 +1,158 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::{Error, Result, Rpc, RpcResponse, RpcTransport};
+
+/// [`RpcTransport`] over the blocking reqwest client
+pub struct ReqwestTransport {
+    host: String,
+    client: reqwest::blocking::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(host: &str) -> Self {
+        Self {
+            host: host.trim_end_matches('/').to_string(),
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+}
+
+impl RpcTransport for ReqwestTransport {
+    fn request(&self, path: &str, body: Option<&[u8]>) -> Result<RpcResponse> {
+        let url = format!("{}/{}", self.host, path);
+        let request = match body {
+            Some(body) => self
+                .client
+                .post(&url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.to_vec()),
+            None => self.client.get(&url),
+        };
+        let response = request.send().map_err(Error::Reqwest)?;
+        let status = response.status().as_u16();
+        let body = response.bytes().map_err(Error::Reqwest)?.to_vec();
+        Ok(RpcResponse { status, body })
+    }
+}
+
+/// Blocking client of the IOST HTTP API, with the same surface as the async [`IOST`](crate::IOST)
+pub type BlockingClient = Rpc<ReqwestTransport>;
+
+impl BlockingClient {
+    pub fn new(host: &str) -> Self {
+        Rpc::with_transport(ReqwestTransport::new(host))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContractStoragePost, IostAction, Status, Tx};
+    use alloc::vec;
+    use mockito::{mock, Matcher};
+
+    fn client() -> BlockingClient {
+        BlockingClient::new(&mockito::server_url())
+    }
+
+    #[test]
+    fn get_token_balance_should_be_ok() {
+        let _m = mock("GET", "/getTokenBalance/admin/iost/false")
+            .with_status(200)
+            .with_body(
+                r#"{
+                    "balance": 982172000,
+                    "frozen_balances": [{"amount": 30, "time": "1545135470000000000"}]
+                }"#,
+            )
+            .create();
+
+        let balance = client().get_token_balance("admin", "iost", false).unwrap();
+        assert_eq!(balance.balance, 982172000.0);
+        assert_eq!(balance.frozen_balances[0].amount, 30.0);
+    }
+
+    #[test]
+    fn get_tx_by_hash_should_be_ok() {
+        let _m = mock(
+            "GET",
+            "/getTxByHash/2ZYqpgswWH2JSpbCVvaDdBg2Fd6xVuaeTRGb8rMR6Gxn",
+        )
+        .with_status(200)
+        .with_body(
+            r#"{
+                "status": "PACKED",
+                "transaction": {
+                    "hash": "2ZYqpgswWH2JSpbCVvaDdBg2Fd6xVuaeTRGb8rMR6Gxn",
+                    "time": "1545135470000000000",
+                    "expiration": "1545135560000000000",
+                    "gas_ratio": 1,
+                    "gas_limit": 1000000,
+                    "delay": "0",
+                    "chain_id": 1024,
+                    "actions": [],
+                    "signers": [],
+                    "publisher": "admin",
+                    "referred_tx": "",
+                    "amount_limit": [],
+                    "tx_receipt": null
+                },
+                "block_number": "10"
+            }"#,
+        )
+        .create();
+
+        let response = client()
+            .get_tx_by_hash("2ZYqpgswWH2JSpbCVvaDdBg2Fd6xVuaeTRGb8rMR6Gxn")
+            .unwrap();
+        assert!(matches!(response.status, Status::PACKED));
+        assert_eq!(response.block_number, "10");
+        assert_eq!(response.transaction.expiration, 1545135560000000000);
+    }
+
+    #[test]
+    fn get_contract_storage_should_be_ok() {
+        let _m = mock("POST", "/getContractStorage")
+            .match_body(Matcher::Json(serde_json::json!({
+                "id": "token.iost",
+                "key": "TIiost",
+                "field": "decimal",
+                "by_longest_chain": false
+            })))
+            .with_status(200)
+            .with_body(r#"{"data": "8", "block_hash": "", "block_number": "10"}"#)
+            .create();
+
+        let post = ContractStoragePost {
+            id: "token.iost".to_string(),
+            key: "TIiost".to_string(),
+            field: "decimal".to_string(),
+            by_longest_chain: false,
+        };
+        let storage = client().get_contract_storage(&post).unwrap();
+        assert_eq!(storage.data, "8");
+    }
+
+    #[test]
+    fn send_tx_should_map_error_response() {
+        let _m = mock("POST", "/sendTx")
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({"publisher": "nobody"}),
+            ))
+            .with_status(500)
+            .with_body(r#"{"code": 2, "message": "validate signature error"}"#)
+            .create();
+
+        let action = IostAction::transfer("nobody", "testaccount", "10", "").unwrap();
+        let mut tx = Tx::new(1545135470000000000, 1545135560000000000, 1024, vec![action]).unwrap();
+        tx.publisher = "nobody".parse().unwrap();
+        match client().send_tx(&tx) {
+            Err(Error::ErrorMessage(message)) => {
+                assert_eq!(message.code, 2);
+                assert_eq!(message.message, "validate signature error");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
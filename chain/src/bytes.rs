@@ -21,6 +21,8 @@ pub enum ReadError {
     NotEnoughBytes,
     /// Not support message type.
     NotSupportMessageType,
+    /// Value not valid for its type, e.g. a malformed account name.
+    InvalidValue,
 }
 
 /// Write bytes.
@@ -1,225 +1,446 @@
-//
-// use alloc::format;
-// use alloc::string::String;
-// #[cfg(feature = "std")]
-// use async_trait::async_trait;
-//
-// #[cfg(feature = "std")]
-// use serde::{Deserialize, Serialize};
-// use crate::{Error, NodeInfo, ErrorMessage, ChainInfo, GasRatio, RamInfo, ContractStoragePost, ContractStorage, ContractStorageFieldsPost, ContractStorageFields, BatchContractStoragePost, BatchContractStorage};
-// use crate::tx_response::TxResponse;
-// use crate::tx::Tx;
-//
-// struct IOST {
-//     host: String,
-//     client: reqwest::Client,
-// }
-//
-// #[async_trait]
-// trait Client {
-//     fn new(host: &str) -> Self;
-//
-//     async fn get<T>(&self, path: &str) -> Result<T, Error>
-//         where
-//             T: 'static + for<'de> Deserialize<'de>;
-//
-//     async fn post<T, R>(&self, path: &str, param: R) -> Result<T, Error>
-//         where
-//             T: 'static + for<'de> Deserialize<'de>,
-//             R: Serialize + Send + Sync;
-// }
-//
-// #[async_trait]
-// impl Client for IOST {
-//     fn new(host: &str) -> Self {
-//         Self {
-//             host: host.to_owned(),
-//             client: reqwest::Client::new(),
-//         }
-//     }
-//
-//     async fn get<T>(&self, path: &str) -> Result<T, Error>
-//         where
-//             T: 'static + for<'de> Deserialize<'de>,
-//     {
-//         let url = format!("{}/{}", self.host, path);
-//         let response = self.client.get(&url).send().await.map_err(Error::Reqwest)?;
-//         if response.status() == 200 {
-//             let result = response.json::<T>().await.map_err(Error::Reqwest)?;
-//             Ok(result)
-//         } else {
-//             let rsp = response
-//                 .json::<ErrorMessage>()
-//                 .await
-//                 .map_err(Error::Reqwest)?;
-//             Err(Error::ErrorMessage(rsp))
-//         }
-//     }
-//
-//     async fn post<T, R>(&self, path: &str, param: R) -> Result<T, Error>
-//         where
-//             T: 'static + for<'de> Deserialize<'de>,
-//             R: Serialize + Send + Sync,
-//     {
-//         let url = format!("{}/{}", self.host, path);
-//         let req = reqwest::Client::new()
-//             .post(&url)
-//             .json(&param)
-//             .send()
-//             .await
-//             .map_err(Error::Reqwest)?;
-//         let code_status = req.status();
-//         if code_status == 200 {
-//             let response = req.json().await.map_err(Error::Reqwest)?;
-//             Ok(response)
-//         } else {
-//             let response = req.json().await.map_err(Error::Reqwest)?;
-//             Err(Error::ErrorMessage(response))
-//         }
-//     }
-// }
-//
-// impl IOST {
-//     pub async fn get_node_info(&self) -> Result<NodeInfo, Error> {
-//         self.get("getNodeInfo").await
-//     }
-//
-//     pub async fn get_chain_info(&self) -> Result<ChainInfo, Error> {
-//         self.get("getChainInfo").await
-//     }
-//
-//     pub async fn get_gas_ratio(&self) -> Result<GasRatio, Error> {
-//         self.get("getGasRatio").await
-//     }
-//
-//     pub async fn get_ram_info(&self) -> Result<RamInfo, Error> {
-//         self.get("getRAMInfo").await
-//     }
-//
-//     pub async fn get_contract_storage(
-//         &self,
-//         par: ContractStoragePost,
-//     ) -> Result<ContractStorage, Error> {
-//         self.post("getContractStorage", &par).await
-//     }
-//
-//     pub async fn get_contract_storage_fields(
-//         &self,
-//         par: ContractStorageFieldsPost,
-//     ) -> Result<ContractStorageFields, Error> {
-//         self.post("getContractStorageFields", &par).await
-//     }
-//
-//     pub async fn get_batch_contract_storage(
-//         &self,
-//         par: BatchContractStoragePost,
-//     ) -> Result<BatchContractStorage, Error> {
-//         self.post("getBatchContractStorage", &par).await
-//     }
-//
-//     pub async fn send_tx(&self, par: Tx) -> Result<TxResponse, Error> {
-//         self.post("sendTx", par).await
-//     }
-// }
-//
-// /// Execute test file command "cargo test iost_basic_test -- --nocapture"
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//     use crate::action::Action;
-//     use crate::amount_limit::AmountLimit;
-//     use crate::signature::Signature;
-//     use crate::{KeyField, BatchContractStoragePost};
-//
-//     #[tokio::test]
-//     async fn iost_basic_get_method_should_be_ok() {
-//         let host = "https://api.iost.io";
-//         let iost = IOST::new(host);
-//         let result = iost.get_node_info().await;
-//         assert!(result.is_ok());
-//         let chain_result = iost.get_chain_info().await;
-//         assert!(chain_result.is_ok());
-//         let gas_result = iost.get_gas_ratio().await;
-//         assert!(gas_result.is_ok());
-//         let ram_result = iost.get_ram_info().await;
-//         assert!(ram_result.is_ok());
-//     }
-//
-//     #[tokio::test]
-//     async fn iost_basic_post_method_should_be_ok() {
-//         let host = "https://api.iost.io";
-//         let iost = IOST::new(host);
-//         let new_post = ContractStoragePost {
-//             id: "token.iost".to_string(),
-//             key: "TIiost".to_string(),
-//             field: "decimal".to_string(),
-//             by_longest_chain: true,
-//         };
-//         let result = iost.get_contract_storage(new_post).await;
-//         assert!(result.is_ok());
-//         let new_post = ContractStorageFieldsPost {
-//             id: "token.iost".to_string(),
-//             key: "TIiost".to_string(),
-//             by_longest_chain: true,
-//         };
-//         let field_result = iost.get_contract_storage_fields(new_post).await;
-//         assert!(field_result.is_ok());
-//         let key = KeyField {
-//             key: "supply".to_string(),
-//             field: "TIiost".to_string(),
-//         };
-//
-//         let key1 = KeyField {
-//             key: "decimal".to_string(),
-//             field: "TIiost".to_string(),
-//         };
-//
-//         let posts = BatchContractStoragePost {
-//             id: "token.iost".to_string(),
-//             key_fields: vec![key, key1],
-//             by_longest_chain: true,
-//         };
-//         let storage_result = iost.get_batch_contract_storage(posts).await;
-//         assert!(storage_result.is_ok());
-//     }
-//
-//     #[tokio::test]
-//     async fn test_send_tx_should_be_ok() {
-//         let host = "https://api.iost.io";
-//         let iost = IOST::new(host);
-//         let action = Action{
-//             contract: "token.iost".to_string(),
-//             action_name: "transfer".to_string(),
-//             data: "[\"iost\", \"testaccount\", \"anothertest\", \"100\", \"this is an example transfer\"]".to_string()
-//         };
-//
-//         let amount_limit = AmountLimit {
-//             token: "*".to_string(),
-//             value: "unlimited".to_string(),
-//         };
-//
-//         let signature = Signature{
-//             algorithm: "ED25519".to_string(),
-//             signature: "lDS+SdM+aiVHbDyXapvrsgyKxFg9mJuHWPZb/INBRWY=".to_string(),
-//             public_key: "/K1HM0OEbfJ4+D3BmalpLmb03WS7BeCz4nVHBNbDrx3/A31aN2RJNxyEKhv+VSoWctfevDNRnL1kadRVxSt8CA==".to_string()
-//         };
-//
-//         let tx = Tx {
-//             time: 1544709662543340000,
-//             expiration: 1544709692318715000,
-//             gas_ratio: 1.0,
-//             gas_limit: 500000.0,
-//             delay: 0,
-//             chain_id: 1024,
-//             actions: vec![action],
-//             amount_limit: vec![amount_limit],
-//             publisher: "testaccount".to_string(),
-//             publisher_sigs: vec![signature],
-//             signers: vec![],
-//             signatures: vec![],
-//         };
-//
-//         let tx_result = iost.send_tx(tx).await;
-//         dbg!(tx_result);
-//     }
-// }
+use alloc::format;
+use alloc::string::{String, ToString};
+use std::time::Instant;
+
+use async_trait::async_trait;
+use keys::keypair::KeyPair;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::confirm::{check_receipt, Confirmation};
+use crate::rpc::{encode_body, parse_response};
+use crate::{
+    Account, AsyncRpcTransport, BatchContractStorage, BatchContractStoragePost, BlockResponse,
+    ChainInfo, ConfirmOptions, Contract, ContractStorage, ContractStorageFields,
+    ContractStorageFieldsPost, ContractStoragePost, Error, GasRatio, GlobalRAMInfo, NodeInfo,
+    Result, RpcResponse, TokenBalance, TransactionResponse, Tx, TxEstimate, TxReceipt, TxResponse,
+};
+
+/// [`AsyncRpcTransport`] over the async reqwest client
+pub struct AsyncReqwestTransport {
+    host: String,
+    client: reqwest::Client,
+}
+
+impl AsyncReqwestTransport {
+    pub fn new(host: &str) -> Self {
+        Self {
+            host: host.trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncRpcTransport for AsyncReqwestTransport {
+    async fn request(&self, path: &str, body: Option<&[u8]>) -> Result<RpcResponse> {
+        let url = format!("{}/{}", self.host, path);
+        let request = match body {
+            Some(body) => self
+                .client
+                .post(&url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.to_vec()),
+            None => self.client.get(&url),
+        };
+        let response = request.send().await.map_err(Error::Reqwest)?;
+        let status = response.status().as_u16();
+        let body = response.bytes().await.map_err(Error::Reqwest)?.to_vec();
+        Ok(RpcResponse { status, body })
+    }
+}
+
+/// Methods of the async client for the endpoints of `iost_endpoints`
+macro_rules! async_endpoints {
+    ($($name:ident($($arg:ident: $ty:ty),*) -> $response:ty = $method:ident($($call:tt)*);)*) => {
+        $(
+            pub async fn $name(&self, $($arg: $ty),*) -> Result<$response> {
+                self.$method($($call)*).await
+            }
+        )*
+    };
+}
+
+/// Async client of the IOST HTTP API, over any [`AsyncRpcTransport`]
+pub struct IOST<T: AsyncRpcTransport = AsyncReqwestTransport> {
+    transport: T,
+}
+
+impl IOST {
+    pub fn new(host: &str) -> Self {
+        Self::with_transport(AsyncReqwestTransport::new(host))
+    }
+}
+
+impl<T: AsyncRpcTransport> IOST<T> {
+    pub fn with_transport(transport: T) -> Self {
+        Self { transport }
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    async fn get<R>(&self, path: &str) -> Result<R>
+    where
+        R: DeserializeOwned,
+    {
+        let response = self.transport.request(path, None).await?;
+        parse_response(response)
+    }
+
+    async fn post<R, P>(&self, path: &str, param: &P) -> Result<R>
+    where
+        R: DeserializeOwned,
+        P: Serialize + ?Sized,
+    {
+        let body = encode_body(param)?;
+        let response = self.transport.request(path, Some(&body)).await?;
+        parse_response(response)
+    }
+
+    iost_endpoints!(async_endpoints);
+
+    /// Dry-runs a copy of `tx` signed by `account` through `execTx`, and estimates its gas and RAM
+    pub async fn estimate_tx(
+        &self,
+        tx: &Tx,
+        account: &str,
+        key_pair: &KeyPair,
+    ) -> Result<TxEstimate> {
+        let dry_run = TxEstimate::dry_run_tx(tx, account, key_pair)?;
+        let receipt = self.exec_tx(&dry_run).await?;
+        TxEstimate::from_receipt(&receipt, &dry_run)
+    }
+
+    /// Polls the node until the transaction `hash`, expiring at `expiration`, reaches the status
+    /// targeted by `options`, and returns its receipt
+    pub async fn wait_for_tx(
+        &self,
+        hash: &str,
+        expiration: i64,
+        options: &ConfirmOptions,
+    ) -> Result<TxReceipt> {
+        let start = Instant::now();
+        loop {
+            let response = self.get_tx_by_hash(hash).await;
+            if let Confirmation::Confirmed(receipt) = options.check(hash, expiration, response)? {
+                let receipt = match receipt {
+                    Some(receipt) => receipt,
+                    None => self.get_tx_receipt_by_tx_hash(hash).await?,
+                };
+                return check_receipt(receipt);
+            }
+            if start.elapsed() >= options.timeout {
+                return Err(Error::TxConfirmTimeout(hash.to_string()));
+            }
+            tokio::time::delay_for(options.interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IostAction, KeyField, Status, StatusCode};
+    use alloc::vec;
+    use mockito::{mock, Matcher};
+
+    const TX_RECEIPT: &str = r#"{
+        "tx_hash": "6eGkZoXPQtYXdxDfiW8HGbfVyBB3wsb9SaaJc5GSJX3a",
+        "gas_usage": 2236,
+        "ram_usage": {"admin": "6"},
+        "status_code": "SUCCESS",
+        "message": "",
+        "returns": ["[\"\"]"],
+        "receipts": [{
+            "func_name": "token.iost/transfer",
+            "content": "[\"iost\",\"admin\",\"testaccount\",\"10\",\"\"]"
+        }]
+    }"#;
+
+    fn client() -> IOST {
+        IOST::new(&mockito::server_url())
+    }
+
+    #[tokio::test]
+    async fn get_chain_info_should_be_ok() {
+        let _m = mock("GET", "/getChainInfo")
+            .with_status(200)
+            .with_body(
+                r#"{
+                    "net_name": "debugnet",
+                    "protocol_version": "1.0",
+                    "chain_id": 1024,
+                    "head_block": "9551",
+                    "head_block_hash": "6eGkZoXPQtYXdxDfiW8HGbfVyBB3wsb9SaaJc5GSJX3a",
+                    "lib_block": "9551",
+                    "lib_block_hash": "6eGkZoXPQtYXdxDfiW8HGbfVyBB3wsb9SaaJc5GSJX3a",
+                    "witness_list": ["IOST2K9GKzVazBRLPTkZSCMcyMayKv7dWgdHD8uuWPzjfPdzj93x6J"],
+                    "lib_witness_list": ["IOST2K9GKzVazBRLPTkZSCMcyMayKv7dWgdHD8uuWPzjfPdzj93x6J"],
+                    "pending_witness_list": [],
+                    "head_block_time": "1545135470000000000",
+                    "lib_block_time": "1545135470000000000"
+                }"#,
+            )
+            .create();
+
+        let chain_info = client().get_chain_info().await.unwrap();
+        assert_eq!(chain_info.chain_id, 1024);
+        assert_eq!(chain_info.head_block, "9551");
+        assert_eq!(chain_info.witness_list.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn get_block_by_number_should_be_ok() {
+        let _m = mock("GET", "/getBlockByNumber/3/true")
+            .with_status(200)
+            .with_body(
+                r#"{
+                    "status": "IRREVERSIBLE",
+                    "block": {
+                        "hash": "6eGkZoXPQtYXdxDfiW8HGbfVyBB3wsb9SaaJc5GSJX3a",
+                        "version": "0",
+                        "parent_hash": "2ZYqpgswWH2JSpbCVvaDdBg2Fd6xVuaeTRGb8rMR6Gxn",
+                        "tx_merkle_hash": "3YcgRDmDA1ibcHMvg5hBPL8bXo5yahkTm1pS7gdQz6bS",
+                        "tx_receipt_merkle_hash": "CHB7CX7y6VEdWawWEfnfNkbs2PTbmaj1FGmKymgfAMDP",
+                        "number": "3",
+                        "witness": "IOST2K9GKzVazBRLPTkZSCMcyMayKv7dWgdHD8uuWPzjfPdzj93x6J",
+                        "time": "1545135470000000000",
+                        "gas_usage": 2236,
+                        "tx_count": "1",
+                        "info": {"mode": 0, "thread": 0, "batch_index": []},
+                        "transactions": [{
+                            "hash": "6eGkZoXPQtYXdxDfiW8HGbfVyBB3wsb9SaaJc5GSJX3a",
+                            "time": "1545135470000000000",
+                            "expiration": "1545135560000000000",
+                            "gas_ratio": 1,
+                            "gas_limit": 1000000,
+                            "delay": "0",
+                            "chain_id": 1024,
+                            "actions": [{
+                                "contract": "token.iost",
+                                "action_name": "transfer",
+                                "data": "[\"iost\",\"admin\",\"testaccount\",\"10\",\"\"]"
+                            }],
+                            "signers": [],
+                            "publisher": "admin",
+                            "referred_tx": "",
+                            "amount_limit": [{"token": "*", "value": "unlimited"}],
+                            "tx_receipt": null
+                        }]
+                    }
+                }"#,
+            )
+            .create();
+
+        let response = client().get_block_by_number(3, true).await.unwrap();
+        assert!(matches!(response.status, Status::IRREVERSIBLE));
+        assert_eq!(response.block.number, "3");
+        assert_eq!(response.block.transactions.len(), 1);
+        let tx = &response.block.transactions[0];
+        assert_eq!(tx.time, 1545135470000000000);
+        assert_eq!(tx.actions[0].action_name, b"transfer".to_vec());
+        assert!(tx.tx_receipt.is_none());
+    }
+
+    #[tokio::test]
+    async fn get_tx_receipt_by_tx_hash_should_be_ok() {
+        let _m = mock(
+            "GET",
+            "/getTxReceiptByTxHash/6eGkZoXPQtYXdxDfiW8HGbfVyBB3wsb9SaaJc5GSJX3a",
+        )
+        .with_status(200)
+        .with_body(TX_RECEIPT)
+        .create();
+
+        let receipt = client()
+            .get_tx_receipt_by_tx_hash("6eGkZoXPQtYXdxDfiW8HGbfVyBB3wsb9SaaJc5GSJX3a")
+            .await
+            .unwrap();
+        assert!(matches!(receipt.status_code, StatusCode::SUCCESS));
+        assert_eq!(receipt.gas_usage, 2236.0);
+        assert_eq!(receipt.ram_usage.get("admin"), Some(&"6".to_string()));
+        assert_eq!(receipt.receipts[0].func_name, "token.iost/transfer");
+    }
+
+    #[tokio::test]
+    async fn get_account_should_be_ok() {
+        let _m = mock("GET", "/getAccount/admin/true")
+            .with_status(200)
+            .with_body(
+                r#"{
+                    "name": "admin",
+                    "balance": 982172000,
+                    "gas_info": {
+                        "current_total": 53681198,
+                        "transferable_gas": 0,
+                        "pledge_gas": 53681198,
+                        "increase_speed": 11,
+                        "limit": 300000000,
+                        "pledged_info": [{"pledger": "admin", "amount": 1000}]
+                    },
+                    "ram_info": {"available": "100000", "used": "2000", "total": "102000"},
+                    "permissions": {
+                        "active": {
+                            "name": "active",
+                            "group_names": [],
+                            "items": [{
+                                "id": "IOST2mCzj85xkSvMf1eoGtrexQcwE6gK8z5xr6Kc48DwxXPCqQJva4",
+                                "is_key_pair": true,
+                                "weight": "100",
+                                "permission": ""
+                            }],
+                            "threshold": "100"
+                        }
+                    },
+                    "groups": {},
+                    "frozen_balances": [],
+                    "vote_infos": []
+                }"#,
+            )
+            .create();
+
+        let account = client().get_account("admin", true).await.unwrap();
+        assert_eq!(account.name, "admin");
+        assert_eq!(account.gas_info.pledged_info.len(), 1);
+        assert_eq!(account.ram_info.used, "2000");
+        assert_eq!(account.permissions["active"].threshold, "100");
+    }
+
+    #[tokio::test]
+    async fn get_batch_contract_storage_should_be_ok() {
+        let _m = mock("POST", "/getBatchContractStorage")
+            .match_body(Matcher::Json(serde_json::json!({
+                "id": "token.iost",
+                "key_fields": [
+                    {"key": "TIiost", "field": "decimal"},
+                    {"key": "TIiost", "field": "supply"}
+                ],
+                "by_longest_chain": true
+            })))
+            .with_status(200)
+            .with_body(r#"{"datas": ["8", "21000000000"], "block_hash": "", "block_number": "3"}"#)
+            .create();
+
+        let post = BatchContractStoragePost {
+            id: "token.iost".to_string(),
+            key_fields: vec![
+                KeyField {
+                    key: "TIiost".to_string(),
+                    field: "decimal".to_string(),
+                },
+                KeyField {
+                    key: "TIiost".to_string(),
+                    field: "supply".to_string(),
+                },
+            ],
+            by_longest_chain: true,
+        };
+        let storage = client().get_batch_contract_storage(&post).await.unwrap();
+        assert_eq!(
+            storage.datas,
+            vec!["8".to_string(), "21000000000".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn send_tx_should_be_ok() {
+        let _m = mock("POST", "/sendTx")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "chain_id": 1024,
+                "actions": [{"contract": "token.iost", "action_name": "transfer"}]
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{"hash": "6eGkZoXPQtYXdxDfiW8HGbfVyBB3wsb9SaaJc5GSJX3a", "pre_tx_receipt": null}"#,
+            )
+            .create();
+
+        let action = IostAction::transfer("admin", "testaccount", "10", "").unwrap();
+        let tx = Tx::new(1545135470000000000, 1545135560000000000, 1024, vec![action]).unwrap();
+        let response = client().send_tx(&tx).await.unwrap();
+        assert_eq!(
+            response.hash,
+            "6eGkZoXPQtYXdxDfiW8HGbfVyBB3wsb9SaaJc5GSJX3a"
+        );
+        assert!(response.pre_tx_receipt.is_none());
+    }
+
+    #[tokio::test]
+    async fn exec_tx_should_be_ok() {
+        let _m = mock("POST", "/execTx")
+            .with_status(200)
+            .with_body(TX_RECEIPT)
+            .create();
+
+        let action = IostAction::transfer("admin", "testaccount", "10", "").unwrap();
+        let tx = Tx::new(1545135470000000000, 1545135560000000000, 1024, vec![action]).unwrap();
+        let receipt = client().exec_tx(&tx).await.unwrap();
+        assert_eq!(receipt.returns.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn error_response_should_be_mapped() {
+        let _m = mock("GET", "/getTxByHash/notexist")
+            .with_status(400)
+            .with_body(r#"{"code": 2, "message": "tx not found"}"#)
+            .create();
+
+        match client().get_tx_by_hash("notexist").await {
+            Err(Error::ErrorMessage(message)) => {
+                assert_eq!(message.code, 2);
+                assert_eq!(message.message, "tx not found");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn wait_for_tx_should_return_receipt() {
+        let _m = mock(
+            "GET",
+            "/getTxByHash/5Gf3oFg3WDyPrAAWRWzvEpXvqCdnuFGDqCLJRpXb4UK1",
+        )
+        .with_status(200)
+        .with_body(
+            r#"{
+                "status": "IRREVERSIBLE",
+                "transaction": {
+                    "hash": "5Gf3oFg3WDyPrAAWRWzvEpXvqCdnuFGDqCLJRpXb4UK1",
+                    "time": "1545135470000000000",
+                    "expiration": "1545135560000000000",
+                    "gas_ratio": 1,
+                    "gas_limit": 1000000,
+                    "delay": "0",
+                    "chain_id": 1024,
+                    "actions": [],
+                    "signers": [],
+                    "publisher": "admin",
+                    "referred_tx": "",
+                    "amount_limit": [],
+                    "tx_receipt": {
+                        "tx_hash": "5Gf3oFg3WDyPrAAWRWzvEpXvqCdnuFGDqCLJRpXb4UK1",
+                        "gas_usage": 2000,
+                        "ram_usage": {},
+                        "status_code": "SUCCESS",
+                        "message": "",
+                        "returns": [],
+                        "receipts": []
+                    }
+                },
+                "block_number": "10"
+            }"#,
+        )
+        .create();
+
+        let receipt = client()
+            .wait_for_tx(
+                "5Gf3oFg3WDyPrAAWRWzvEpXvqCdnuFGDqCLJRpXb4UK1",
+                0,
+                &ConfirmOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(receipt.gas_usage, 2000.0);
+    }
+}
@@ -0,0 +1,75 @@
+use alloc::string::ToString;
+use core::time::Duration;
+
+use chrono::Utc;
+
+use crate::Error::{ErrorMessage, TxExecError, TxExpired};
+use crate::{Result, Status, StatusCode, TransactionResponse, TxReceipt};
+
+/// Part of the message the node answers for a transaction it does not know (yet)
+const TX_NOT_FOUND: &str = "not found";
+
+/// Options of waiting for a transaction to be confirmed
+#[derive(Clone, Debug)]
+pub struct ConfirmOptions {
+    /// status to wait for, `PACKED` or `IRREVERSIBLE`
+    pub target: Status,
+    /// interval between two polls of the node
+    pub interval: Duration,
+    /// time to give up after, even if the transaction has not expired
+    pub timeout: Duration,
+}
+
+impl Default for ConfirmOptions {
+    fn default() -> Self {
+        ConfirmOptions {
+            target: Status::PACKED,
+            interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(90),
+        }
+    }
+}
+
+/// State of a transaction after one poll of the node
+pub(crate) enum Confirmation {
+    Pending,
+    /// confirmed, with the receipt if the node returned it along with the transaction
+    Confirmed(Option<TxReceipt>),
+}
+
+impl ConfirmOptions {
+    /// Evaluates the response of `getTxByHash` for the transaction `hash` expiring at `expiration`
+    pub(crate) fn check(
+        &self,
+        hash: &str,
+        expiration: i64,
+        response: Result<TransactionResponse>,
+    ) -> Result<Confirmation> {
+        match response {
+            Ok(response) => match (&response.status, &self.target) {
+                (Status::IRREVERSIBLE, _) | (Status::PACKED, Status::PACKED) => {
+                    return Ok(Confirmation::Confirmed(response.transaction.tx_receipt));
+                }
+                // packed transactions no longer expire
+                (Status::PACKED, _) => return Ok(Confirmation::Pending),
+                _ => {}
+            },
+            // the node answers not found until it knows the transaction
+            Err(ErrorMessage(ref message)) if message.message.contains(TX_NOT_FOUND) => {}
+            Err(err) => return Err(err),
+        }
+        if Utc::now().timestamp_nanos() > expiration {
+            Err(TxExpired(hash.to_string()))
+        } else {
+            Ok(Confirmation::Pending)
+        }
+    }
+}
+
+/// Turns a receipt into a failure if the transaction did not succeed
+pub(crate) fn check_receipt(receipt: TxReceipt) -> Result<TxReceipt> {
+    match receipt.status_code {
+        StatusCode::SUCCESS => Ok(receipt),
+        _ => Err(TxExecError(receipt.status_code, receipt.message)),
+    }
+}
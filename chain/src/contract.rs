@@ -0,0 +1,92 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use lite_json::{parse_json, JsonValue, Serialize as _};
+
+use crate::abi::ABI;
+use crate::contract_action::{field, json_string, string_arg};
+use crate::Error::JsonParserError;
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+/// Language of the contracts deployed with `system.iost/setCode`
+pub const JAVASCRIPT: &str = "javascript";
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Contract {
+    /// contract id
+    pub id: String,
+    /// contract code
+    pub code: String,
+    /// programming language of the contract
+    pub language: String,
+    /// contract version
+    pub version: String,
+    /// ABIs of the contract
+    pub abis: Vec<ABI>,
+}
+
+impl Contract {
+    /// JavaScript contract to deploy, its id is assigned by the chain
+    pub fn javascript<C: Into<String>, V: Into<String>>(
+        code: C,
+        version: V,
+        abis: Vec<ABI>,
+    ) -> Self {
+        Contract {
+            id: String::new(),
+            code: code.into(),
+            language: JAVASCRIPT.into(),
+            version: version.into(),
+            abis,
+        }
+    }
+
+    /// Contract as `system.iost/setCode` expects it:
+    /// `{"ID":..,"info":{"lang":..,"version":..,"abi":[..]},"code":..}`, where an empty `ID` is
+    /// omitted as the chain's own encoding does
+    pub fn to_json(&self) -> String {
+        let info = JsonValue::Object(vec![
+            ("lang".chars().collect(), string_arg(&self.language)),
+            ("version".chars().collect(), string_arg(&self.version)),
+            (
+                "abi".chars().collect(),
+                JsonValue::Array(self.abis.iter().map(ABI::to_json).collect()),
+            ),
+        ]);
+        let mut contract = Vec::new();
+        if !self.id.is_empty() {
+            contract.push(("ID".chars().collect(), string_arg(&self.id)));
+        }
+        contract.push(("info".chars().collect(), info));
+        contract.push(("code".chars().collect(), string_arg(&self.code)));
+        let contract = JsonValue::Object(contract);
+        String::from_utf8_lossy(&contract.serialize()).into_owned()
+    }
+
+    /// Parses the argument of `system.iost/setCode`, where an empty `ID` may be omitted
+    pub fn from_json(s: &str) -> crate::Result<Self> {
+        let contract = parse_json(s).map_err(|_| JsonParserError())?;
+        let info = field(&contract, "info").ok_or_else(JsonParserError)?;
+        let abis = match field(info, "abi") {
+            None => Vec::new(),
+            Some(JsonValue::Array(abis)) => abis
+                .iter()
+                .map(ABI::from_json)
+                .collect::<crate::Result<_>>()?,
+            Some(_) => return Err(JsonParserError()),
+        };
+        Ok(Contract {
+            id: match field(&contract, "ID") {
+                None => String::new(),
+                id => json_string(id)?,
+            },
+            code: json_string(field(&contract, "code"))?,
+            language: json_string(field(info, "lang"))?,
+            version: json_string(field(info, "version"))?,
+            abis,
+        })
+    }
+}
@@ -0,0 +1,150 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use lite_json::{parse_json, JsonValue, NumberValue, Serialize};
+
+use keys::public::PublicKey;
+
+use crate::Error::{JsonParserError, Keys, UnexpectedAction};
+use crate::IostAction;
+
+/// A typed call of a contract function, encoded as the JSON array of its arguments
+pub trait ContractAction: Sized {
+    /// Id of the contract, e.g. `token.iost`
+    const CONTRACT: &'static str;
+    /// Name of the called function, e.g. `transfer`
+    const ACTION_NAME: &'static str;
+
+    /// Arguments of the call, in the order and JSON types of the ABI
+    fn to_args(&self) -> Vec<JsonValue>;
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self>;
+
+    fn to_action(&self) -> crate::Result<IostAction> {
+        call(Self::CONTRACT, Self::ACTION_NAME, self.to_args())
+    }
+
+    /// Decodes `action`, failing with `UnexpectedAction` for a call of another function
+    fn from_action(action: &IostAction) -> crate::Result<Self> {
+        if !is_call(action, Self::CONTRACT, Self::ACTION_NAME) {
+            return Err(UnexpectedAction(format!(
+                "{}/{}",
+                action.contract,
+                String::from_utf8_lossy(&action.action_name)
+            )));
+        }
+        Self::from_args(&parse_args(&action.data)?)
+    }
+}
+
+/// Whether `action` calls the function `action_name` of `contract`
+pub fn is_call(action: &IostAction, contract: &str, action_name: &str) -> bool {
+    action.contract == contract && action.action_name == action_name.as_bytes()
+}
+
+/// Action calling the function `action_name` of `contract` with `args`
+pub(crate) fn call(
+    contract: &str,
+    action_name: &str,
+    args: Vec<JsonValue>,
+) -> crate::Result<IostAction> {
+    Ok(IostAction {
+        contract: contract.parse()?,
+        action_name: action_name.as_bytes().to_vec(),
+        data: JsonValue::Array(args).serialize(),
+    })
+}
+
+/// Parses the JSON array of arguments of an action
+pub(crate) fn parse_args(data: &[u8]) -> crate::Result<Vec<JsonValue>> {
+    let data = core::str::from_utf8(data).map_err(|_| JsonParserError())?;
+    match parse_json(data) {
+        Ok(JsonValue::Array(args)) => Ok(args),
+        _ => Err(JsonParserError()),
+    }
+}
+
+pub(crate) fn string_arg<T: ToString + ?Sized>(value: &T) -> JsonValue {
+    JsonValue::String(value.to_string().chars().collect())
+}
+
+pub(crate) fn number_arg(value: i64) -> JsonValue {
+    JsonValue::Number(NumberValue {
+        integer: value,
+        fraction: 0,
+        fraction_length: 0,
+        exponent: 0,
+    })
+}
+
+pub(crate) fn arg_string(args: &[JsonValue], index: usize) -> crate::Result<String> {
+    match args.get(index) {
+        Some(JsonValue::String(chars)) => Ok(chars.iter().collect()),
+        _ => Err(JsonParserError()),
+    }
+}
+
+/// String argument parsed into `T`, e.g. an `AccountName` or an `Amount`
+pub(crate) fn arg_parse<T: FromStr<Err = crate::Error>>(
+    args: &[JsonValue],
+    index: usize,
+) -> crate::Result<T> {
+    arg_string(args, index)?.parse()
+}
+
+/// Integer number argument
+pub(crate) fn arg_i64(args: &[JsonValue], index: usize) -> crate::Result<i64> {
+    match args.get(index) {
+        Some(JsonValue::Number(number)) if number.fraction_length == 0 && number.exponent == 0 => {
+            Ok(number.integer)
+        }
+        _ => Err(JsonParserError()),
+    }
+}
+
+/// Base58 public key argument
+pub(crate) fn arg_key(args: &[JsonValue], index: usize) -> crate::Result<PublicKey> {
+    arg_string(args, index)?.parse().map_err(Keys)
+}
+
+pub(crate) fn arg_bool(args: &[JsonValue], index: usize) -> crate::Result<bool> {
+    match args.get(index) {
+        Some(JsonValue::Boolean(value)) => Ok(*value),
+        _ => Err(JsonParserError()),
+    }
+}
+
+/// Field `key` of a JSON object
+pub(crate) fn field<'a>(object: &'a JsonValue, key: &str) -> Option<&'a JsonValue> {
+    match object {
+        JsonValue::Object(fields) => fields
+            .iter()
+            .find(|(name, _)| name.iter().copied().eq(key.chars()))
+            .map(|(_, value)| value),
+        _ => None,
+    }
+}
+
+/// String JSON value, e.g. a `field` of an object
+pub(crate) fn json_string(value: Option<&JsonValue>) -> crate::Result<String> {
+    match value {
+        Some(JsonValue::String(chars)) => Ok(chars.iter().collect()),
+        _ => Err(JsonParserError()),
+    }
+}
+
+pub(crate) fn json_strings(value: Option<&JsonValue>) -> crate::Result<Vec<String>> {
+    match value {
+        Some(JsonValue::Array(items)) => items.iter().map(|item| json_string(Some(item))).collect(),
+        _ => Err(JsonParserError()),
+    }
+}
+
+pub(crate) fn json_bool(value: Option<&JsonValue>) -> crate::Result<bool> {
+    match value {
+        Some(JsonValue::Boolean(value)) => Ok(*value),
+        _ => Err(JsonParserError()),
+    }
+}
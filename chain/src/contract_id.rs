@@ -0,0 +1,229 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::{convert::TryFrom, fmt, str::FromStr};
+
+use codec::{Decode, Encode, Input, Output};
+#[cfg(feature = "json")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Error::InvalidContractId;
+use crate::{NumberBytes, Read, ReadError, SerializeData, Write, WriteError};
+
+/// Prefix of the ids of deployed contracts, followed by the base58 hash of the deploying transaction
+pub const CONTRACT_ID_PREFIX: &str = "Contract";
+
+/// Suffix of the ids of system contracts
+pub const SYSTEM_CONTRACT_SUFFIX: &str = ".iost";
+
+/// Id of a contract: a system contract like `token.iost`, or a deployed contract, `Contract`
+/// followed by the base58 hash of the transaction which deployed it.
+///
+/// The default id is empty.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ContractId(String);
+
+impl ContractId {
+    pub fn new<T: Into<String>>(id: T) -> crate::Result<Self> {
+        let id = id.into();
+        let valid = if let Some(name) = id.strip_suffix(SYSTEM_CONTRACT_SUFFIX) {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_'))
+        } else if let Some(hash) = id.strip_prefix(CONTRACT_ID_PREFIX) {
+            matches!(bs58::decode(hash).into_vec(), Ok(hash) if hash.len() == 32)
+        } else {
+            false
+        };
+        if !valid {
+            return Err(InvalidContractId(id));
+        }
+        Ok(ContractId(id))
+    }
+
+    /// Id of the contract deployed by the transaction of hash `tx_hash`, in base58
+    pub fn from_tx_hash(tx_hash: &str) -> crate::Result<Self> {
+        ContractId::new(CONTRACT_ID_PREFIX.to_string() + tx_hash)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether this is a system contract, e.g. `token.iost`
+    pub fn is_system(&self) -> bool {
+        self.0.ends_with(SYSTEM_CONTRACT_SUFFIX)
+    }
+
+    fn new_or_empty(id: String) -> crate::Result<Self> {
+        if id.is_empty() {
+            Ok(ContractId::default())
+        } else {
+            ContractId::new(id)
+        }
+    }
+}
+
+impl FromStr for ContractId {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        ContractId::new(s)
+    }
+}
+
+impl TryFrom<&str> for ContractId {
+    type Error = crate::Error;
+
+    fn try_from(value: &str) -> crate::Result<Self> {
+        ContractId::new(value)
+    }
+}
+
+impl TryFrom<String> for ContractId {
+    type Error = crate::Error;
+
+    fn try_from(value: String) -> crate::Result<Self> {
+        ContractId::new(value)
+    }
+}
+
+impl fmt::Display for ContractId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for ContractId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<ContractId> for String {
+    fn from(id: ContractId) -> Self {
+        id.0
+    }
+}
+
+impl PartialEq<str> for ContractId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for ContractId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Encoded as the bytes of the id, like a `String`
+impl Encode for ContractId {
+    fn size_hint(&self) -> usize {
+        self.0.as_bytes().size_hint()
+    }
+
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        self.0.as_bytes().encode_to(dest)
+    }
+}
+
+impl Decode for ContractId {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        let id = String::from_utf8(Vec::<u8>::decode(input)?)
+            .map_err(|_| codec::Error::from("invalid IOST contract id"))?;
+        ContractId::new_or_empty(id).map_err(|_| codec::Error::from("invalid IOST contract id"))
+    }
+}
+
+impl NumberBytes for ContractId {
+    fn num_bytes(&self) -> usize {
+        self.0.num_bytes()
+    }
+}
+
+impl Write for ContractId {
+    fn write(&self, bytes: &mut [u8], pos: &mut usize) -> Result<(), WriteError> {
+        self.0.write(bytes, pos)
+    }
+}
+
+impl SerializeData for ContractId {}
+
+impl Read for ContractId {
+    fn read(bytes: &[u8], pos: &mut usize) -> Result<Self, ReadError> {
+        ContractId::new_or_empty(String::read(bytes, pos)?).map_err(|_| ReadError::InvalidValue)
+    }
+}
+
+#[cfg(feature = "json")]
+impl Serialize for ContractId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'de> Deserialize<'de> for ContractId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        ContractId::new_or_empty(id)
+            .map_err(|_| serde::de::Error::custom("invalid IOST contract id"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DEPLOYED: &str = "ContractCvwZq8SJPqygvSu5SSVNsuWqMGTDyKBPr6Gd74crJiiR";
+
+    #[test]
+    fn contract_id_should_follow_iost_rules() {
+        for id in ["token.iost", "vote_producer.iost", "ram.iost", DEPLOYED].iter() {
+            assert_eq!(id.parse::<ContractId>().unwrap().to_string(), *id);
+        }
+        assert!(ContractId::new("token.iost").unwrap().is_system());
+        assert!(!ContractId::new(DEPLOYED).unwrap().is_system());
+        assert_eq!(
+            ContractId::from_tx_hash(&DEPLOYED[CONTRACT_ID_PREFIX.len()..]).unwrap(),
+            DEPLOYED
+        );
+
+        for id in [
+            "",
+            "iost",
+            ".iost",
+            "Token.iost",
+            "token.iost.iost",
+            "eosio.token",
+            "Contract",
+            "ContractCvwZq8SJPqygvSu5SSVNsu",
+            "Contract0vwZq8SJPqygvSu5SSVNsuWqMGTDyKBPr6Gd74crJiiR",
+        ]
+        .iter()
+        {
+            assert!(matches!(
+                id.parse::<ContractId>(),
+                Err(InvalidContractId(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn contract_id_should_round_trip() {
+        let id: ContractId = "token.iost".parse().unwrap();
+        let bytes = id.to_serialize_data().unwrap();
+        assert_eq!(ContractId::read(&bytes, &mut 0).unwrap(), id);
+        assert_eq!(ContractId::decode(&mut &id.encode()[..]).unwrap(), id);
+        assert!(ContractId::decode(&mut &"iost".to_string().encode()[..]).is_err());
+
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""token.iost""#);
+        assert_eq!(
+            serde_json::from_str::<ContractId>(r#""token.iost""#).unwrap(),
+            id
+        );
+        assert!(serde_json::from_str::<ContractId>(r#""token""#).is_err());
+    }
+}
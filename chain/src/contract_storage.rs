@@ -0,0 +1,71 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::KeyField;
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+#[cfg_attr(feature = "json", derive(Serialize))]
+pub struct ContractStoragePost {
+    /// contract id
+    pub id: String,
+    /// the key of StateDB
+    pub key: String,
+    /// the field of StateDB[key], if StateDB[key] is a map
+    pub field: String,
+    /// true - get data from the longest chain; false - get data from irreversible blocks
+    pub by_longest_chain: bool,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ContractStorage {
+    /// the value of StateDB[key] or StateDB[key][field], encoded as a string
+    pub data: String,
+    /// hash of the block the data is read from
+    pub block_hash: String,
+    /// number of the block the data is read from
+    pub block_number: String,
+}
+
+#[cfg_attr(feature = "json", derive(Serialize))]
+pub struct ContractStorageFieldsPost {
+    /// contract id
+    pub id: String,
+    /// the key of StateDB
+    pub key: String,
+    /// true - get data from the longest chain; false - get data from irreversible blocks
+    pub by_longest_chain: bool,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ContractStorageFields {
+    /// the fields of the map StateDB[key]
+    pub fields: Vec<String>,
+    /// hash of the block the data is read from
+    pub block_hash: String,
+    /// number of the block the data is read from
+    pub block_number: String,
+}
+
+#[cfg_attr(feature = "json", derive(Serialize))]
+pub struct BatchContractStoragePost {
+    /// contract id
+    pub id: String,
+    /// the keys and fields to query
+    pub key_fields: Vec<KeyField>,
+    /// true - get data from the longest chain; false - get data from irreversible blocks
+    pub by_longest_chain: bool,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct BatchContractStorage {
+    /// the values, in the same order as the requested key_fields
+    pub datas: Vec<String>,
+    /// hash of the block the data is read from
+    pub block_hash: String,
+    /// number of the block the data is read from
+    pub block_number: String,
+}
@@ -0,0 +1,37 @@
+//! Endpoints of the IOST HTTP API, described once for the blocking [`Rpc`](crate::Rpc) and the
+//! async `IOST`
+
+/// Hands every endpoint, as `name(args) -> Response = get(path)` or `= post(path, body)`, to the
+/// macro `$implement`, which turns them into the methods of a client
+macro_rules! iost_endpoints {
+    ($implement:ident) => {
+        $implement! {
+            get_node_info() -> NodeInfo = get("getNodeInfo");
+            get_chain_info() -> ChainInfo = get("getChainInfo");
+            get_gas_ratio() -> GasRatio = get("getGasRatio");
+            get_ram_info() -> GlobalRAMInfo = get("getRAMInfo");
+            get_block_by_hash(hash: &str, complete: bool) -> BlockResponse =
+                get(&format!("getBlockByHash/{}/{}", hash, complete));
+            get_block_by_number(number: i64, complete: bool) -> BlockResponse =
+                get(&format!("getBlockByNumber/{}/{}", number, complete));
+            get_tx_by_hash(hash: &str) -> TransactionResponse =
+                get(&format!("getTxByHash/{}", hash));
+            get_tx_receipt_by_tx_hash(hash: &str) -> TxReceipt =
+                get(&format!("getTxReceiptByTxHash/{}", hash));
+            get_account(name: &str, by_longest_chain: bool) -> Account =
+                get(&format!("getAccount/{}/{}", name, by_longest_chain));
+            get_token_balance(account: &str, token: &str, by_longest_chain: bool) -> TokenBalance =
+                get(&format!("getTokenBalance/{}/{}/{}", account, token, by_longest_chain));
+            get_contract(id: &str, by_longest_chain: bool) -> Contract =
+                get(&format!("getContract/{}/{}", id, by_longest_chain));
+            get_contract_storage(par: &ContractStoragePost) -> ContractStorage =
+                post("getContractStorage", par);
+            get_contract_storage_fields(par: &ContractStorageFieldsPost) -> ContractStorageFields =
+                post("getContractStorageFields", par);
+            get_batch_contract_storage(par: &BatchContractStoragePost) -> BatchContractStorage =
+                post("getBatchContractStorage", par);
+            send_tx(tx: &Tx) -> TxResponse = post("sendTx", tx);
+            exec_tx(tx: &Tx) -> TxReceipt = post("execTx", tx);
+        }
+    };
+}
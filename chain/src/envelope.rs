@@ -0,0 +1,187 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+use crate::Error::{
+    BytesReadError, InvalidEnvelopeDigest, InvalidPublisherSignature, InvalidSignature,
+};
+use crate::{
+    AccountName, AccountPermission, NumberBytes, Read, SerializeData, Signature, Tx, Write,
+};
+
+/// Transaction waiting for signatures, carried to offline signers
+#[derive(Clone, Default, Debug, Read, Write, NumberBytes, SerializeData)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[iost_root_path = "crate"]
+pub struct TxEnvelope {
+    /// the transaction, with the signatures collected so far
+    pub tx: Tx,
+    /// signers expected to sign, e.g. "acct@active"
+    pub signers: Vec<AccountPermission>,
+    /// expected publisher
+    pub publisher: AccountName,
+    /// SHA3-256 digest signed by the signers. Hex encoding
+    pub digest: String,
+}
+
+impl TxEnvelope {
+    pub fn new(tx: Tx) -> crate::Result<Self> {
+        let digest = hex::encode(tx.digest(false)?);
+        Ok(TxEnvelope {
+            signers: tx.signers.clone(),
+            publisher: tx.publisher.clone(),
+            tx,
+            digest,
+        })
+    }
+
+    /// Checks the digest, signers and publisher match the transaction
+    pub fn check(&self) -> crate::Result<()> {
+        if hex::encode(self.tx.digest(false)?) != self.digest
+            || self.signers != self.tx.signers
+            || self.publisher != self.tx.publisher
+        {
+            return Err(InvalidEnvelopeDigest());
+        }
+        Ok(())
+    }
+
+    /// Digest of the transaction to be signed by the publisher, once all signers have signed
+    pub fn publisher_digest(&self) -> crate::Result<[u8; 32]> {
+        self.tx.digest(true)
+    }
+
+    /// Merges the signature of a signer, after checking it against the digest
+    pub fn merge(&mut self, signature: Signature) -> crate::Result<()> {
+        let digest = hex::decode(&self.digest).map_err(|_| InvalidEnvelopeDigest())?;
+        if signature.verify(&digest).is_err() {
+            return Err(InvalidSignature());
+        }
+        if !self
+            .tx
+            .signatures
+            .iter()
+            .any(|s| s.public_key == signature.public_key)
+        {
+            self.tx.signatures.push(signature);
+            self.tx.publisher_sigs.clear();
+        }
+        Ok(())
+    }
+
+    /// Merges the signature of the publisher, after checking it against the publisher digest
+    pub fn merge_publisher(&mut self, signature: Signature) -> crate::Result<()> {
+        if signature.verify(&self.publisher_digest()?).is_err() {
+            return Err(InvalidPublisherSignature());
+        }
+        self.tx.publisher_sigs.push(signature);
+        Ok(())
+    }
+
+    /// Returns the signed transaction, ready for `sendTx`
+    pub fn into_tx(self) -> crate::Result<Tx> {
+        if self.tx.signatures.len() < self.signers.len() {
+            return Err(InvalidSignature());
+        }
+        if self.tx.publisher_sigs.is_empty() {
+            return Err(InvalidPublisherSignature());
+        }
+        self.tx.verify()?;
+        Ok(self.tx)
+    }
+
+    /// Imports an envelope from the binary encoding, checking its digest
+    pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
+        let envelope = TxEnvelope::read(bytes, &mut 0).map_err(BytesReadError)?;
+        envelope.check()?;
+        Ok(envelope)
+    }
+
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> crate::Result<String> {
+        serde_json::to_string(self).map_err(|_| crate::Error::JsonParserError())
+    }
+
+    /// Imports an envelope from JSON, checking its digest
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        let envelope: TxEnvelope =
+            serde_json::from_str(json).map_err(|_| crate::Error::JsonParserError())?;
+        envelope.check()?;
+        Ok(envelope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IostAction;
+    use alloc::string::ToString;
+    use alloc::vec;
+    use keys::algorithm;
+    use keys::keypair::KeyPair;
+
+    fn envelope() -> TxEnvelope {
+        let mut tx = Tx::new(
+            1544709662543340000,
+            1544709692318715000,
+            1024,
+            vec![IostAction::transfer("treasury", "lispczz3", "100", "").unwrap()],
+        )
+        .unwrap();
+        tx.publisher = "admin".parse().unwrap();
+        tx.add_signer("treasury@active").unwrap();
+        TxEnvelope::new(tx).unwrap()
+    }
+
+    #[test]
+    fn envelope_should_round_trip_and_merge_signatures() {
+        let signer_key: KeyPair = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".parse().unwrap();
+        let publisher_key = base64::decode("gkpobuI3gbFGstgfdymLBQAGR67ulguDzNmLXEJSWaGUNL5J0z5qJUdsPJdqm+uyDIrEWD2Ym4dY9lv8g0FFZg==").unwrap();
+        let publisher_key = KeyPair::from_secret_key(algorithm::ED25519, &publisher_key).unwrap();
+
+        // on the air-gapped machine
+        let exported = envelope().to_serialize_data().unwrap();
+        let offline = TxEnvelope::from_bytes(&exported).unwrap();
+        let mut digest = [0u8; 32];
+        digest.copy_from_slice(&hex::decode(&offline.digest).unwrap());
+        let signature = Signature::sign(&digest, &signer_key).unwrap();
+
+        let mut envelope = TxEnvelope::from_json(&envelope().to_json().unwrap()).unwrap();
+        assert!(envelope.clone().into_tx().is_err());
+        envelope.merge(signature).unwrap();
+        let publisher_signature =
+            Signature::sign(&envelope.publisher_digest().unwrap(), &publisher_key).unwrap();
+        envelope.merge_publisher(publisher_signature).unwrap();
+
+        let tx = envelope.into_tx().unwrap();
+        assert_eq!(tx.signatures.len(), 1);
+        assert_eq!(tx.publisher_sigs.len(), 1);
+        assert!(tx.verify().is_ok());
+    }
+
+    #[test]
+    fn envelope_should_reject_wrong_signature_and_digest() {
+        let key: KeyPair = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".parse().unwrap();
+        let mut envelope = envelope();
+
+        let signature = Signature::sign(&[0u8; 32], &key).unwrap();
+        assert!(matches!(
+            envelope.merge(signature.clone()),
+            Err(InvalidSignature())
+        ));
+        assert!(matches!(
+            envelope.merge_publisher(signature),
+            Err(InvalidPublisherSignature())
+        ));
+
+        envelope.tx.gas_limit = 2000000.0;
+        assert!(matches!(envelope.check(), Err(InvalidEnvelopeDigest())));
+        assert!(matches!(
+            TxEnvelope::from_json(&envelope.to_json().unwrap()),
+            Err(InvalidEnvelopeDigest())
+        ));
+    }
+}
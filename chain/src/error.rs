@@ -1,4 +1,4 @@
-use crate::{ErrorMessage, ParseNameError, ReadError, WriteError};
+use crate::{ErrorMessage, ParseNameError, ReadError, StatusCode, WriteError};
 use alloc::string::String;
 
 pub type Result<T> = core::result::Result<T, Error>;
@@ -9,25 +9,60 @@ pub enum Error {
     BytesWriteError(WriteError),
 
     JsonParserError(),
+    ///Error request message
+    #[cfg(feature = "client")]
+    Reqwest(reqwest::Error),
+    ///Error of a custom rpc transport
+    RpcTransportError(String),
     ///Error response message
     ErrorMessage(ErrorMessage),
 
     ParseNameErr(ParseNameError),
+    ///Account name not of 5 to 11 characters of a-z, 0-9 and _, or malformed account@permission
+    InvalidAccountName(String),
+    ///Action not of the expected contract/function, by `contract/function`
+    UnexpectedAction(String),
+    ///Contract id neither a system contract like token.iost nor a deployed Contract... id
+    InvalidContractId(String),
 
     FixedParseOverflow(),
     FixedParseAbnormalChar(),
     FixedParseAmountFormat(),
     FixedParseDivideByZero(),
     FixedParseDoubleDot(),
+    ///RAM amount not positive, or not left for sale, in bytes
+    InvalidRAMAmount(i64),
+    ///RAM info whose buy price is below the price of one byte implied by its sell price
+    InvalidRAMPrice(),
+    ///Amount limit of a transaction smaller than its own transfers of the token
+    AmountLimitExceeded(String),
+    ///Action spending tokens other than by a transfer, so that its amount limit must be stated,
+    ///by `contract/function`
+    AmountLimitRequired(String),
+
+    ///Failed execution of a transaction, with the message of its receipt
+    TxExecError(StatusCode, String),
+    ///Transaction expired before being packed, by hash
+    TxExpired(String),
+    ///Transaction not confirmed within the timeout, by hash
+    TxConfirmTimeout(String),
 
     InvalidSignature(),
     InvalidPublisherSignature(),
+    ///Signature of a signer failing to verify, by index in `signatures`
+    SignatureVerifyFailed(usize),
+    ///Signature of the publisher failing to verify, by index in `publisher_sigs`
+    PublisherSignatureVerifyFailed(usize),
+    ///Error of the keys, e.g. a malformed key or signature
+    Keys(keys::error::Error),
+    InvalidEnvelopeDigest(),
 
     InvalidSPVStartBlock(i64),
     IOSTBlockError(),
 
     IOSTBlockVerifyError(String),
-    IOSTInvalidBlockSignature(),
+    ///Wrong producer signature, by block number
+    IOSTInvalidBlockSignature(i64),
     IOSTUpdateEpochError(String),
     IOSTBlockWitnessError(String),
 }
@@ -0,0 +1,205 @@
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Error::{JsonParserError, TxExecError};
+use crate::{Amount, AmountLimit, Result, StatusCode, Tx, TxReceipt};
+use keys::keypair::KeyPair;
+
+/// Suggested gas limit is the dry-run usage raised by this ratio
+pub const GAS_LIMIT_MARGIN: f64 = 1.2;
+/// Minimum gas limit accepted by the chain
+pub const MIN_GAS_LIMIT: f64 = 50000.0;
+
+/// Gas and RAM used by a dry-run of a transaction through `execTx`
+#[derive(Clone, Debug)]
+pub struct TxEstimate {
+    /// GAS consumption of the dry-run
+    pub gas_usage: f64,
+    /// RAM consumption of the dry-run by account name, negative when RAM is released
+    pub ram_usage: BTreeMap<String, i64>,
+    /// suggested gas limit of the transaction
+    pub gas_limit: f64,
+    /// suggested amount limit of the transaction, covering the RAM and tokens spent by the
+    /// publisher and signers
+    pub amount_limit: Vec<AmountLimit>,
+}
+
+impl TxEstimate {
+    /// Returns a throwaway copy of `tx` with unlimited amount limit, signed by `account`. The
+    /// signatures of signers cover the original amount limit, so they are dropped
+    pub fn dry_run_tx(tx: &Tx, account: &str, key_pair: &KeyPair) -> Result<Tx> {
+        let mut dry_run = tx.clone();
+        dry_run.amount_limit = vec![AmountLimit::unlimited()];
+        dry_run.signatures = vec![];
+        dry_run.publisher_sigs = vec![];
+        dry_run.sign(account.to_string(), key_pair)?;
+        Ok(dry_run)
+    }
+
+    /// Parses the receipt of a dry-run of `tx`
+    pub fn from_receipt(receipt: &TxReceipt, tx: &Tx) -> Result<Self> {
+        if receipt.status_code != StatusCode::SUCCESS {
+            return Err(TxExecError(
+                receipt.status_code.clone(),
+                receipt.message.clone(),
+            ));
+        }
+
+        let mut payers = vec![tx.publisher.as_str()];
+        for signer in &tx.signers {
+            payers.push(signer.account.as_str());
+        }
+
+        let mut ram_usage = BTreeMap::new();
+        let mut ram_limit = 0;
+        for (account, ram) in &receipt.ram_usage {
+            let ram = ram.parse::<i64>().map_err(|_| JsonParserError())?;
+            if ram > 0 && payers.contains(&account.as_str()) {
+                ram_limit += ram;
+            }
+            ram_usage.insert(account.clone(), ram);
+        }
+
+        let mut token_usage: BTreeMap<String, Amount> = BTreeMap::new();
+        for receipt in &receipt.receipts {
+            if receipt.func_name != "token.iost/transfer"
+                && receipt.func_name != "token.iost/transferFreeze"
+            {
+                continue;
+            }
+            let args: Vec<serde_json::Value> =
+                serde_json::from_str(&receipt.content).map_err(|_| JsonParserError())?;
+            let (token, from, amount) = match (args.first(), args.get(1), args.get(3)) {
+                (
+                    Some(serde_json::Value::String(token)),
+                    Some(serde_json::Value::String(from)),
+                    Some(serde_json::Value::String(amount)),
+                ) => (token, from, amount),
+                _ => return Err(JsonParserError()),
+            };
+            if !payers.contains(&from.as_str()) {
+                continue;
+            }
+            let spent = token_usage.entry(token.clone()).or_default();
+            *spent = spent.checked_add(&amount.parse()?)?;
+        }
+
+        let mut amount_limit = Vec::new();
+        for (token, spent) in token_usage {
+            amount_limit.push(AmountLimit::exact(token, spent)?);
+        }
+        if ram_limit > 0 {
+            amount_limit.push(AmountLimit::exact("ram", Amount::new(ram_limit, 0)?)?);
+        }
+
+        // f64::ceil needs std
+        let gas_limit = receipt.gas_usage * GAS_LIMIT_MARGIN;
+        let gas_limit = match gas_limit as i64 as f64 {
+            truncated if truncated < gas_limit => truncated + 1.0,
+            truncated => truncated,
+        };
+        Ok(TxEstimate {
+            gas_usage: receipt.gas_usage,
+            ram_usage,
+            gas_limit: if gas_limit < MIN_GAS_LIMIT {
+                MIN_GAS_LIMIT
+            } else {
+                gas_limit
+            },
+            amount_limit,
+        })
+    }
+
+    /// Sets the suggested gas limit and amount limit on `tx`
+    pub fn apply(&self, tx: &mut Tx) {
+        tx.gas_limit = self.gas_limit;
+        tx.amount_limit = self.amount_limit.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IostAction, Receipt};
+
+    fn receipt(status_code: StatusCode) -> TxReceipt {
+        let mut ram_usage = BTreeMap::new();
+        ram_usage.insert("admin".to_string(), "312".to_string());
+        ram_usage.insert("lispczz3".to_string(), "-20".to_string());
+        ram_usage.insert("token.iost".to_string(), "15".to_string());
+        TxReceipt {
+            tx_hash: "".to_string(),
+            gas_usage: 60512.0,
+            ram_usage,
+            status_code,
+            message: "".to_string(),
+            returns: vec![],
+            receipts: vec![
+                Receipt {
+                    func_name: "token.iost/transfer".to_string(),
+                    content: r#"["iost","admin","lispczz3","100.5",""]"#.to_string(),
+                },
+                Receipt {
+                    func_name: "token.iost/transfer".to_string(),
+                    content: r#"["iost","admin","lispczz4","0.25",""]"#.to_string(),
+                },
+                Receipt {
+                    func_name: "token.iost/transfer".to_string(),
+                    content: r#"["iost","lispczz3","admin","7",""]"#.to_string(),
+                },
+            ],
+        }
+    }
+
+    fn tx() -> Tx {
+        let action = IostAction::transfer("admin", "lispczz3", "100.5", "").unwrap();
+        let mut tx = Tx::new(1598918258274417000, 1598918348274417000, 1024, vec![action]).unwrap();
+        tx.publisher = "admin".parse().unwrap();
+        tx
+    }
+
+    #[test]
+    fn estimate_should_suggest_limits() {
+        let estimate = TxEstimate::from_receipt(&receipt(StatusCode::SUCCESS), &tx()).unwrap();
+        assert_eq!(estimate.gas_usage, 60512.0);
+        assert_eq!(estimate.ram_usage["lispczz3"], -20);
+        assert_eq!(estimate.gas_limit, 72615.0);
+        assert_eq!(estimate.amount_limit.len(), 2);
+        assert_eq!(estimate.amount_limit[0].token, "iost");
+        assert_eq!(estimate.amount_limit[0].value, "100.75");
+        assert_eq!(estimate.amount_limit[1].token, "ram");
+        assert_eq!(estimate.amount_limit[1].value, "312");
+
+        let mut tx = tx();
+        estimate.apply(&mut tx);
+        assert_eq!(tx.gas_limit, 72615.0);
+        assert_eq!(tx.amount_limit[0].value, "100.75");
+    }
+
+    #[test]
+    fn dry_run_tx_should_drop_stale_signatures() {
+        let key_pair: KeyPair = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".parse().unwrap();
+        let mut tx = tx();
+        tx.add_signer("lispczz3@active").unwrap();
+        tx.sign_as_signer(&key_pair).unwrap();
+        tx.sign("admin".to_string(), &key_pair).unwrap();
+
+        let dry_run = TxEstimate::dry_run_tx(&tx, "admin", &key_pair).unwrap();
+        assert_eq!(dry_run.amount_limit, vec![AmountLimit::unlimited()]);
+        assert_eq!(dry_run.signers, tx.signers);
+        assert!(dry_run.signatures.is_empty());
+        assert_eq!(dry_run.publisher_sigs.len(), 1);
+        assert!(dry_run.verify().is_ok());
+        assert_eq!(tx.signatures.len(), 1);
+    }
+
+    #[test]
+    fn estimate_should_reject_failed_receipt() {
+        match TxEstimate::from_receipt(&receipt(StatusCode::BALANCE_NOT_ENOUGH), &tx()) {
+            Err(TxExecError(code, _)) => assert_eq!(code, StatusCode::BALANCE_NOT_ENOUGH),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
@@ -1,10 +1,10 @@
 use alloc::string::String;
 
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct FrozenBalance {
     /// the amount
     pub amount: f64,
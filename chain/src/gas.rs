@@ -0,0 +1,136 @@
+//! Typed actions of the `gas.iost` contract: IOST pledged for gas
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use lite_json::JsonValue;
+
+use crate::contract_action::{arg_parse, parse_args, string_arg};
+use crate::Error::UnexpectedAction;
+use crate::{AccountName, Amount, AmountLimit, ContractAction, IostAction};
+
+/// Id of the gas contract
+pub const GAS_CONTRACT: &str = "gas.iost";
+
+/// Pledge of `amount` IOST by `pledger`, for the gas of `to`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pledge {
+    pub pledger: AccountName,
+    pub to: AccountName,
+    pub amount: Amount,
+}
+
+impl ContractAction for Pledge {
+    const CONTRACT: &'static str = GAS_CONTRACT;
+    const ACTION_NAME: &'static str = "pledge";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.pledger),
+            string_arg(&self.to),
+            string_arg(&self.amount),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(Pledge {
+            pledger: arg_parse(args, 0)?,
+            to: arg_parse(args, 1)?,
+            amount: arg_parse(args, 2)?,
+        })
+    }
+}
+
+impl Pledge {
+    /// Limit of the IOST spent by the pledge, to be stated with `Tx::with_amount_limit`
+    pub fn amount_limit(&self) -> crate::Result<AmountLimit> {
+        AmountLimit::exact("iost", self.amount)
+    }
+}
+
+/// Withdrawal of `amount` IOST pledged by `pledger` for the gas of `from`. The IOST stays
+/// frozen for three days
+#[derive(Clone, Debug, PartialEq)]
+pub struct Unpledge {
+    pub pledger: AccountName,
+    pub from: AccountName,
+    pub amount: Amount,
+}
+
+impl ContractAction for Unpledge {
+    const CONTRACT: &'static str = GAS_CONTRACT;
+    const ACTION_NAME: &'static str = "unpledge";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.pledger),
+            string_arg(&self.from),
+            string_arg(&self.amount),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(Unpledge {
+            pledger: arg_parse(args, 0)?,
+            from: arg_parse(args, 1)?,
+            amount: arg_parse(args, 2)?,
+        })
+    }
+}
+
+/// Any decoded action of the gas contract
+#[derive(Clone, Debug, PartialEq)]
+pub enum GasAction {
+    Pledge(Pledge),
+    Unpledge(Unpledge),
+}
+
+impl GasAction {
+    pub fn from_action(action: &IostAction) -> crate::Result<Self> {
+        let action_name = String::from_utf8_lossy(&action.action_name);
+        let unexpected = || UnexpectedAction(format!("{}/{}", action.contract, action_name));
+        if action.contract != GAS_CONTRACT {
+            return Err(unexpected());
+        }
+        let args = parse_args(&action.data)?;
+        Ok(match action_name.as_ref() {
+            Pledge::ACTION_NAME => GasAction::Pledge(Pledge::from_args(&args)?),
+            Unpledge::ACTION_NAME => GasAction::Unpledge(Unpledge::from_args(&args)?),
+            _ => return Err(unexpected()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gas_actions_should_round_trip() {
+        let pledge = Pledge {
+            pledger: "admin".parse().unwrap(),
+            to: "lispczz3".parse().unwrap(),
+            amount: "10.5".parse().unwrap(),
+        };
+        let action = pledge.to_action().unwrap();
+        assert_eq!(action.contract, GAS_CONTRACT);
+        assert_eq!(action.data, br#"["admin","lispczz3","10.5"]"#.to_vec());
+        assert_eq!(pledge.amount_limit().unwrap().value, "10.5");
+        assert_eq!(
+            GasAction::from_action(&action).unwrap(),
+            GasAction::Pledge(pledge)
+        );
+
+        let unpledge = Unpledge {
+            pledger: "admin".parse().unwrap(),
+            from: "lispczz3".parse().unwrap(),
+            amount: "1".parse().unwrap(),
+        };
+        let action = unpledge.to_action().unwrap();
+        assert_eq!(action.action_name, b"unpledge");
+        assert_eq!(Unpledge::from_action(&action).unwrap(), unpledge);
+        assert!(Pledge::from_action(&action).is_err());
+    }
+}
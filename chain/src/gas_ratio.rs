@@ -0,0 +1,11 @@
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct GasRatio {
+    /// the lowest gas ratio in the last block
+    pub lowest_gas_ratio: f64,
+    /// the median gas ratio in the last block
+    pub median_gas_ratio: f64,
+}
@@ -2,11 +2,11 @@ use alloc::string::String;
 use alloc::vec::Vec;
 
 use crate::item::Item;
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct Group {
     /// name of the group
     pub name: String,
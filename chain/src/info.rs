@@ -1,10 +1,10 @@
 use alloc::vec::Vec;
 
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct Info {
     /// mode of concurrency; 0 - non-concurrent; 1 - concurrent
     pub mode: i32,
@@ -1,9 +1,9 @@
 use alloc::string::String;
 
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 use serde::Serialize;
 
-#[cfg_attr(feature = "std", derive(Serialize))]
+#[cfg_attr(feature = "json", derive(Serialize))]
 pub struct KeyField {
     /// the key of StateDB
     pub key: String,
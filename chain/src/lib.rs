@@ -2,18 +2,43 @@
 
 extern crate alloc;
 
+#[cfg(feature = "json")]
+#[macro_use]
+mod endpoints;
+
 pub mod abi;
+pub mod account;
+pub mod account_name;
 pub mod action;
+pub mod amount;
 pub mod amount_limit;
+pub mod auth;
+pub mod block;
+#[cfg(feature = "client")]
+pub mod blocking_client;
 pub mod bytes;
+pub mod chain_info;
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(feature = "std")]
+pub mod confirm;
+pub mod contract;
+pub mod contract_action;
+pub mod contract_id;
+pub mod contract_storage;
 
 mod chain_test;
 
 pub mod spv;
 pub mod verify;
 
+pub mod envelope;
 pub mod error;
+#[cfg(feature = "json")]
+pub mod estimate;
 pub mod frozen_balance;
+pub mod gas;
+pub mod gas_ratio;
 pub mod group;
 pub mod info;
 pub mod item;
@@ -21,32 +46,54 @@ pub mod key_field;
 pub mod message;
 pub mod names;
 pub mod net_work_info;
+pub mod node_info;
 pub mod permission;
 pub mod pledge_info;
+pub mod ram;
 pub mod ram_info;
 pub mod receipts;
+#[cfg(feature = "json")]
+pub mod rpc;
 pub mod signature;
+#[cfg(feature = "std")]
+pub mod signer;
 pub mod status;
 pub mod status_code;
+pub mod system;
 pub mod test;
 pub mod time_point;
+pub mod token;
+pub mod token_balance;
 pub mod transaction;
+pub mod transport;
 pub mod tx;
 pub mod tx_receipt;
 pub mod tx_response;
 pub mod unsigned_int;
 pub mod vote_info;
-
-mod get_chain_info;
+pub mod vote_producer;
 
 pub use iost_derive::*;
 
 pub use self::{
-    abi::*, action::*, amount_limit::*, bytes::*, error::*, frozen_balance::*, group::*, info::*,
-    item::*, key_field::*, message::*, names::*, net_work_info::*, permission::*, pledge_info::*,
-    ram_info::*, receipts::*, signature::*, status::*, status_code::*, transaction::*, tx::*,
-    tx_receipt::*, tx_response::*, unsigned_int::*, vote_info::*,
+    abi::*, account::*, account_name::*, action::*, amount::*, amount_limit::*, block::*, bytes::*,
+    chain_info::*, contract::*, contract_action::*, contract_id::*, contract_storage::*,
+    envelope::*, error::*, frozen_balance::*, gas_ratio::*, group::*, info::*, item::*,
+    key_field::*, message::*, names::*, net_work_info::*, node_info::*, permission::*,
+    pledge_info::*, ram_info::*, receipts::*, signature::*, status::*, status_code::*,
+    token_balance::*, transaction::*, transport::*, tx::*, tx_receipt::*, tx_response::*,
+    unsigned_int::*, vote_info::*,
+};
+
+#[cfg(feature = "client")]
+pub use self::{
+    blocking_client::{BlockingClient, ReqwestTransport},
+    client::{AsyncReqwestTransport, IOST},
 };
+#[cfg(feature = "std")]
+pub use self::{confirm::ConfirmOptions, signer::AsyncSigner};
+#[cfg(feature = "json")]
+pub use self::{estimate::TxEstimate, rpc::Rpc};
 
 use alloc::vec;
 use alloc::vec::Vec;
@@ -171,7 +171,7 @@ struct NameVisitor<T: FromStr<Err = ParseNameError> + From<u64> + core::fmt::Dis
     core::marker::PhantomData<T>,
 );
 
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 impl<'de, T> serde::de::Visitor<'de> for NameVisitor<T>
 where
     T: FromStr<Err = ParseNameError> + From<u64> + core::fmt::Display,
@@ -251,7 +251,7 @@ macro_rules! declare_name_types {
             }
         }
 
-        #[cfg(feature = "std")]
+        #[cfg(feature = "json")]
         impl<'de> serde::Deserialize<'de> for $ident {
             #[inline]
             fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -262,7 +262,7 @@ macro_rules! declare_name_types {
             }
         }
 
-        #[cfg(feature = "std")]
+        #[cfg(feature = "json")]
         impl serde::Serialize for $ident {
             fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
             where
@@ -313,25 +313,8 @@ macro_rules! declare_name_types {
 
 declare_name_types! {
     Name
-    AccountName
     PermissionName
     ScopeName
     TableName
     ActionName
 }
-
-impl From<ScopeName> for AccountName {
-    #[inline]
-    fn from(scope: ScopeName) -> Self {
-        let value: u64 = scope.into();
-        value.into()
-    }
-}
-
-impl From<AccountName> for ScopeName {
-    #[inline]
-    fn from(name: AccountName) -> Self {
-        let value: u64 = name.into();
-        value.into()
-    }
-}
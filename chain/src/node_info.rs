@@ -0,0 +1,22 @@
+use alloc::string::String;
+
+use crate::net_work_info::NetWork;
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct NodeInfo {
+    /// build time of the server executable
+    pub build_time: String,
+    /// iServer git hash
+    pub git_hash: String,
+    /// current mode of the server, "ModeInit" or "ModeNormal"
+    pub mode: String,
+    /// network connection information
+    pub network: NetWork,
+    /// iServer code version
+    pub code_version: String,
+    /// current timestamp of the server
+    pub server_time: String,
+}
@@ -0,0 +1,300 @@
+//! Typed actions of the `ram.iost` contract, and offline quotes of its RAM prices
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use lite_json::JsonValue;
+
+use crate::contract_action::{arg_i64, arg_parse, number_arg, parse_args, string_arg};
+use crate::Error::{InvalidRAMAmount, InvalidRAMPrice, JsonParserError, UnexpectedAction};
+use crate::{
+    AccountName, Amount, AmountLimit, ContractAction, GlobalRAMInfo, IostAction, IOST_DECIMAL,
+};
+
+/// Id of the RAM contract
+pub const RAM_CONTRACT: &str = "ram.iost";
+
+/// Purchase of `bytes` of RAM for `account`, paid by `payer`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Buy {
+    pub payer: AccountName,
+    pub account: AccountName,
+    pub bytes: i64,
+}
+
+impl ContractAction for Buy {
+    const CONTRACT: &'static str = RAM_CONTRACT;
+    const ACTION_NAME: &'static str = "buy";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.payer),
+            string_arg(&self.account),
+            number_arg(self.bytes),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(Buy {
+            payer: arg_parse(args, 0)?,
+            account: arg_parse(args, 1)?,
+            bytes: arg_i64(args, 2)?,
+        })
+    }
+}
+
+impl Buy {
+    /// Limit of the IOST spent by the purchase at the prices of `market`, to be stated with
+    /// `Tx::with_amount_limit`. The price moves until the transaction is executed, so it is
+    /// usually raised by a margin.
+    pub fn amount_limit(&self, market: &RamMarket) -> crate::Result<AmountLimit> {
+        AmountLimit::exact("iost", market.buy_cost(self.bytes)?)
+    }
+}
+
+/// Sale of `bytes` of RAM of `account`, paid to `receiver`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sell {
+    pub account: AccountName,
+    pub receiver: AccountName,
+    pub bytes: i64,
+}
+
+impl ContractAction for Sell {
+    const CONTRACT: &'static str = RAM_CONTRACT;
+    const ACTION_NAME: &'static str = "sell";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.account),
+            string_arg(&self.receiver),
+            number_arg(self.bytes),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(Sell {
+            account: arg_parse(args, 0)?,
+            receiver: arg_parse(args, 1)?,
+            bytes: arg_i64(args, 2)?,
+        })
+    }
+}
+
+/// Loan of `bytes` of RAM bought by `from` to `to`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lend {
+    pub from: AccountName,
+    pub to: AccountName,
+    pub bytes: i64,
+}
+
+impl ContractAction for Lend {
+    const CONTRACT: &'static str = RAM_CONTRACT;
+    const ACTION_NAME: &'static str = "lend";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.from),
+            string_arg(&self.to),
+            number_arg(self.bytes),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(Lend {
+            from: arg_parse(args, 0)?,
+            to: arg_parse(args, 1)?,
+            bytes: arg_i64(args, 2)?,
+        })
+    }
+}
+
+/// Any decoded action of the RAM contract
+#[derive(Clone, Debug, PartialEq)]
+pub enum RamAction {
+    Buy(Buy),
+    Sell(Sell),
+    Lend(Lend),
+}
+
+impl RamAction {
+    pub fn from_action(action: &IostAction) -> crate::Result<Self> {
+        let action_name = String::from_utf8_lossy(&action.action_name);
+        let unexpected = || UnexpectedAction(format!("{}/{}", action.contract, action_name));
+        if action.contract != RAM_CONTRACT {
+            return Err(unexpected());
+        }
+        let args = parse_args(&action.data)?;
+        Ok(match action_name.as_ref() {
+            Buy::ACTION_NAME => RamAction::Buy(Buy::from_args(&args)?),
+            Sell::ACTION_NAME => RamAction::Sell(Sell::from_args(&args)?),
+            Lend::ACTION_NAME => RamAction::Lend(Lend::from_args(&args)?),
+            _ => return Err(unexpected()),
+        })
+    }
+}
+
+/// RAM market of the RAM contract, a Bancor market between its IOST `balance` and the RAM
+/// `left_space` for sale: buying `n` bytes costs `balance * n / (left_space - n)` plus the fee,
+/// selling them pays `balance * n / (left_space + n)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RamMarket {
+    /// IOST held by the RAM contract
+    pub balance: f64,
+    /// RAM bytes left for sale
+    pub left_space: i64,
+    /// fee of buying RAM, as a ratio of its price
+    pub fee_rate: f64,
+}
+
+impl RamMarket {
+    /// Market of a `getRAMInfo` snapshot. The balance is implied by the sell price, the return of
+    /// selling one byte, and the fee by the buy price, the cost of buying one byte. Fails with
+    /// `InvalidRAMPrice` when the buy price is below the price of that byte.
+    pub fn from_info(info: &GlobalRAMInfo) -> crate::Result<Self> {
+        let left_space = info
+            .available_ram
+            .parse::<i64>()
+            .map_err(|_| JsonParserError())?;
+        if left_space <= 1 {
+            return Err(InvalidRAMAmount(left_space));
+        }
+        let balance = info.sell_price * (left_space + 1) as f64;
+        let fee_rate = info.buy_price * (left_space - 1) as f64 / balance - 1.0;
+        // a rounding error of the node is tolerated below a fee of 0
+        if !fee_rate.is_finite() || fee_rate < -1e-9 || fee_rate >= 1.0 {
+            return Err(InvalidRAMPrice());
+        }
+        Ok(RamMarket {
+            balance,
+            left_space,
+            fee_rate: fee_rate.max(0.0),
+        })
+    }
+
+    /// IOST paid for buying `bytes`, fee included, rounded up
+    pub fn buy_cost(&self, bytes: i64) -> crate::Result<Amount> {
+        if bytes <= 0 || bytes >= self.left_space {
+            return Err(InvalidRAMAmount(bytes));
+        }
+        let price = self.balance * bytes as f64 / (self.left_space - bytes) as f64;
+        iost_amount(price * (1.0 + self.fee_rate), true)
+    }
+
+    /// IOST received for selling `bytes`, rounded down
+    pub fn sell_return(&self, bytes: i64) -> crate::Result<Amount> {
+        if bytes <= 0 {
+            return Err(InvalidRAMAmount(bytes));
+        }
+        let price = self.balance * bytes as f64 / (self.left_space + bytes) as f64;
+        iost_amount(price, false)
+    }
+}
+
+/// Amount of `iost` IOST, rounded to 10^-8 IOST up or down. `f64::ceil` needs std.
+fn iost_amount(iost: f64, round_up: bool) -> crate::Result<Amount> {
+    let value = iost * 1e8;
+    if !value.is_finite() || value < 0.0 || value >= i64::MAX as f64 {
+        return Err(crate::Error::FixedParseOverflow());
+    }
+    let units = value as i64;
+    let units = if round_up && (units as f64) < value {
+        units + 1
+    } else {
+        units
+    };
+    Amount::new(units, IOST_DECIMAL)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn info() -> GlobalRAMInfo {
+        GlobalRAMInfo {
+            used_ram: "37328732000".to_string(),
+            available_ram: "100000000000".to_string(),
+            total_ram: "137438953472".to_string(),
+            sell_price: 0.0001,
+            buy_price: 0.000102,
+        }
+    }
+
+    #[test]
+    fn ram_actions_should_round_trip() {
+        let buy = Buy {
+            payer: "admin".parse().unwrap(),
+            account: "lispczz3".parse().unwrap(),
+            bytes: 1024,
+        };
+        let action = buy.to_action().unwrap();
+        assert_eq!(action.data, br#"["admin","lispczz3",1024]"#.to_vec());
+        assert_eq!(
+            RamAction::from_action(&action).unwrap(),
+            RamAction::Buy(buy)
+        );
+
+        let lend = Lend {
+            from: "admin".parse().unwrap(),
+            to: "lispczz3".parse().unwrap(),
+            bytes: 10,
+        };
+        let action = lend.to_action().unwrap();
+        assert_eq!(action.action_name, b"lend");
+        assert_eq!(Lend::from_action(&action).unwrap(), lend);
+        assert!(Sell::from_action(&action).is_err());
+    }
+
+    #[test]
+    fn ram_market_should_quote_prices() {
+        let market = RamMarket::from_info(&info()).unwrap();
+        assert_eq!(market.balance, 10000000.0001);
+        assert!((market.fee_rate - 0.02).abs() < 1e-9);
+
+        // one byte is quoted at the prices of the snapshot
+        assert_eq!(market.buy_cost(1).unwrap(), "0.000102".parse().unwrap());
+        assert_eq!(market.sell_return(1).unwrap(), "0.0001".parse().unwrap());
+
+        let cost = market.buy_cost(1024).unwrap();
+        assert_eq!(cost.to_string(), "0.10444801");
+        let proceeds = market.sell_return(1024).unwrap();
+        assert_eq!(proceeds.to_string(), "0.10239999");
+        assert!(proceeds < cost);
+
+        // buying pushes the price up
+        let large = market.buy_cost(50000000000).unwrap();
+        assert!(large.value() > cost.value() * 48828125);
+
+        assert!(matches!(market.buy_cost(0), Err(InvalidRAMAmount(0))));
+        assert!(market.buy_cost(100000000000).is_err());
+        assert!(market.sell_return(-1).is_err());
+
+        let buy = Buy {
+            payer: "admin".parse().unwrap(),
+            account: "lispczz3".parse().unwrap(),
+            bytes: 1024,
+        };
+        let limit = buy.amount_limit(&market).unwrap();
+        assert_eq!(limit.token, "iost");
+        assert_eq!(limit.value, "0.10444801");
+    }
+
+    #[test]
+    fn ram_market_should_reject_inconsistent_prices() {
+        let mut info = info();
+        info.buy_price = 0.00009;
+        assert!(matches!(
+            RamMarket::from_info(&info),
+            Err(InvalidRAMPrice())
+        ));
+        info.available_ram = "1".to_string();
+        assert!(matches!(
+            RamMarket::from_info(&info),
+            Err(InvalidRAMAmount(1))
+        ));
+    }
+}
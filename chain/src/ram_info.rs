@@ -1,10 +1,10 @@
 use alloc::string::String;
 
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct RAMInfo {
     /// RAM bytes available for use
     pub available: String,
@@ -13,3 +13,18 @@ pub struct RAMInfo {
     /// RAM bytes total
     pub total: String,
 }
+
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct GlobalRAMInfo {
+    /// RAM bytes sold
+    pub used_ram: String,
+    /// RAM bytes still available for sale
+    pub available_ram: String,
+    /// total RAM bytes in the system
+    pub total_ram: String,
+    /// current price of selling RAM, in IOST per byte
+    pub sell_price: f64,
+    /// current price of buying RAM, in IOST per byte
+    pub buy_price: f64,
+}
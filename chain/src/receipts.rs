@@ -1,14 +1,12 @@
 use alloc::string::String;
 
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct Receipt {
     /// ABI function name
-    #[serde(rename = "funcName")]
-    #[cfg(feature = "std")]
     pub func_name: String,
     /// content
     pub content: String,
@@ -0,0 +1,411 @@
+use alloc::format;
+#[cfg(feature = "std")]
+use alloc::string::ToString;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use keys::keypair::KeyPair;
+use serde::{de::DeserializeOwned, Serialize};
+
+#[cfg(feature = "std")]
+use crate::confirm::{check_receipt, Confirmation};
+#[cfg(feature = "std")]
+use crate::ConfirmOptions;
+use crate::{
+    Account, BatchContractStorage, BatchContractStoragePost, BlockResponse, ChainInfo, Contract,
+    ContractStorage, ContractStorageFields, ContractStorageFieldsPost, ContractStoragePost, Error,
+    ErrorMessage, GasRatio, GlobalRAMInfo, NodeInfo, Result, RpcResponse, RpcTransport,
+    TokenBalance, TransactionResponse, Tx, TxEstimate, TxReceipt, TxResponse,
+};
+
+/// JSON body of a POST to the node
+pub(crate) fn encode_body<P>(param: &P) -> Result<Vec<u8>>
+where
+    P: Serialize + ?Sized,
+{
+    serde_json::to_vec(param).map_err(|_| Error::JsonParserError())
+}
+
+/// Decodes a response of the node, mapping a non-200 status to its `ErrorMessage`
+pub(crate) fn parse_response<R>(response: RpcResponse) -> Result<R>
+where
+    R: DeserializeOwned,
+{
+    if response.status == 200 {
+        serde_json::from_slice(&response.body).map_err(|_| Error::JsonParserError())
+    } else {
+        let rsp: ErrorMessage =
+            serde_json::from_slice(&response.body).map_err(|_| Error::JsonParserError())?;
+        Err(Error::ErrorMessage(rsp))
+    }
+}
+
+/// Methods of the blocking client for the endpoints of `iost_endpoints`
+macro_rules! blocking_endpoints {
+    ($($name:ident($($arg:ident: $ty:ty),*) -> $response:ty = $method:ident($($call:tt)*);)*) => {
+        $(
+            pub fn $name(&self, $($arg: $ty),*) -> Result<$response> {
+                self.$method($($call)*)
+            }
+        )*
+    };
+}
+
+/// Typed IOST HTTP API over any [`RpcTransport`]
+pub struct Rpc<T: RpcTransport> {
+    transport: T,
+}
+
+impl<T: RpcTransport> Rpc<T> {
+    pub fn with_transport(transport: T) -> Self {
+        Self { transport }
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    fn get<R>(&self, path: &str) -> Result<R>
+    where
+        R: DeserializeOwned,
+    {
+        let response = self.transport.request(path, None)?;
+        parse_response(response)
+    }
+
+    fn post<R, P>(&self, path: &str, param: &P) -> Result<R>
+    where
+        R: DeserializeOwned,
+        P: Serialize + ?Sized,
+    {
+        let body = encode_body(param)?;
+        let response = self.transport.request(path, Some(&body))?;
+        parse_response(response)
+    }
+
+    iost_endpoints!(blocking_endpoints);
+
+    /// Dry-runs a copy of `tx` signed by `account` through `execTx`, and estimates its gas and RAM
+    pub fn estimate_tx(&self, tx: &Tx, account: &str, key_pair: &KeyPair) -> Result<TxEstimate> {
+        let dry_run = TxEstimate::dry_run_tx(tx, account, key_pair)?;
+        let receipt = self.exec_tx(&dry_run)?;
+        TxEstimate::from_receipt(&receipt, &dry_run)
+    }
+
+    /// Polls the node until the transaction `hash`, expiring at `expiration`, reaches the status
+    /// targeted by `options`, and returns its receipt
+    #[cfg(feature = "std")]
+    pub fn wait_for_tx(
+        &self,
+        hash: &str,
+        expiration: i64,
+        options: &ConfirmOptions,
+    ) -> Result<TxReceipt> {
+        let start = Instant::now();
+        loop {
+            let response = self.get_tx_by_hash(hash);
+            if let Confirmation::Confirmed(receipt) = options.check(hash, expiration, response)? {
+                let receipt = match receipt {
+                    Some(receipt) => receipt,
+                    None => self.get_tx_receipt_by_tx_hash(hash)?,
+                };
+                return check_receipt(receipt);
+            }
+            if start.elapsed() >= options.timeout {
+                return Err(Error::TxConfirmTimeout(hash.to_string()));
+            }
+            std::thread::sleep(options.interval);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::StatusCode;
+    use crate::{IostAction, MockTransport, Status};
+    use alloc::string::String;
+    use alloc::vec;
+
+    #[test]
+    fn get_chain_info_should_be_ok() {
+        let transport = MockTransport::new();
+        transport.respond(
+            "getChainInfo",
+            200,
+            r#"{
+                "net_name": "debugnet",
+                "protocol_version": "1.0",
+                "chain_id": 1024,
+                "head_block": "9",
+                "head_block_hash": "4Uft6qUD2J6EGSe2Mg9QgnYfNqpjxQ1axqBXDqyBVZ9u",
+                "lib_block": "9",
+                "lib_block_hash": "4Uft6qUD2J6EGSe2Mg9QgnYfNqpjxQ1axqBXDqyBVZ9u",
+                "witness_list": ["IOSTfQFocqDn7VrKV7vvPqhAQGyeFU9XMYo5SNn5yQbdbzC75wM7C"],
+                "lib_witness_list": [],
+                "pending_witness_list": [],
+                "head_block_time": "1545135470000000000",
+                "lib_block_time": "1545135470000000000"
+            }"#,
+        );
+
+        let rpc = Rpc::with_transport(transport);
+        let info = rpc.get_chain_info().unwrap();
+        assert_eq!(info.chain_id, 1024);
+        assert_eq!(info.net_name, "debugnet");
+        assert_eq!(rpc.transport().requests()[0].1, None);
+    }
+
+    #[test]
+    fn send_tx_should_post_json_and_map_error() {
+        let transport = MockTransport::new();
+        transport.respond(
+            "sendTx",
+            500,
+            r#"{"code": 2, "message": "validate signature error"}"#,
+        );
+
+        let action = IostAction::transfer("admin", "testaccount", "10", "").unwrap();
+        let tx = Tx::new(1545135470000000000, 1545135560000000000, 1024, vec![action]).unwrap();
+        let rpc = Rpc::with_transport(transport);
+        match rpc.send_tx(&tx) {
+            Err(Error::ErrorMessage(message)) => assert_eq!(message.code, 2),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let requests = rpc.transport().requests();
+        let body: serde_json::Value =
+            serde_json::from_slice(requests[0].1.as_ref().unwrap()).unwrap();
+        assert_eq!(body["chain_id"], 1024);
+    }
+
+    #[test]
+    fn estimate_tx_should_dry_run_signed_copy() {
+        let transport = MockTransport::new();
+        transport.respond(
+            "execTx",
+            200,
+            r#"{
+                "tx_hash": "",
+                "gas_usage": 40000,
+                "ram_usage": {"admin": "100"},
+                "status_code": "SUCCESS",
+                "message": "",
+                "returns": ["[]"],
+                "receipts": [{
+                    "func_name": "token.iost/transfer",
+                    "content": "[\"iost\",\"admin\",\"testaccount\",\"10\",\"\"]"
+                }]
+            }"#,
+        );
+
+        let action = IostAction::transfer("admin", "testaccount", "10", "").unwrap();
+        let mut tx = Tx::new(1545135470000000000, 1545135560000000000, 1024, vec![action]).unwrap();
+        tx.amount_limit = vec![];
+        let key_pair: KeyPair = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".parse().unwrap();
+        let rpc = Rpc::with_transport(transport);
+        let estimate = rpc.estimate_tx(&tx, "admin", &key_pair).unwrap();
+        assert_eq!(estimate.gas_limit, 50000.0);
+        assert_eq!(estimate.amount_limit[0].value, "10");
+        assert_eq!(estimate.amount_limit[1].token, "ram");
+        assert!(tx.publisher_sigs.is_empty());
+
+        let requests = rpc.transport().requests();
+        let body: serde_json::Value =
+            serde_json::from_slice(requests[0].1.as_ref().unwrap()).unwrap();
+        assert_eq!(body["publisher"], "admin");
+        assert_eq!(body["amount_limit"][0]["value"], "unlimited");
+        assert_eq!(body["publisher_sigs"].as_array().unwrap().len(), 1);
+    }
+
+    const HASH: &str = "2ZYqpgswWH2JSpbCVvaDdBg2Fd6xVuaeTRGb8rMR6Gxn";
+
+    fn tx_response(status: &str, tx_receipt: &str) -> String {
+        format!(
+            r#"{{
+                "status": "{}",
+                "transaction": {{
+                    "hash": "{}",
+                    "time": "1545135470000000000",
+                    "expiration": "1545135560000000000",
+                    "gas_ratio": 1,
+                    "gas_limit": 1000000,
+                    "delay": "0",
+                    "chain_id": 1024,
+                    "actions": [],
+                    "signers": [],
+                    "publisher": "admin",
+                    "referred_tx": "",
+                    "amount_limit": [],
+                    "tx_receipt": {}
+                }},
+                "block_number": "10"
+            }}"#,
+            status, HASH, tx_receipt
+        )
+    }
+
+    fn receipt(status_code: &str) -> String {
+        format!(
+            r#"{{
+                "tx_hash": "{}",
+                "gas_usage": 2000,
+                "ram_usage": {{}},
+                "status_code": "{}",
+                "message": "",
+                "returns": [],
+                "receipts": []
+            }}"#,
+            HASH, status_code
+        )
+    }
+
+    #[test]
+    fn get_tx_by_hash_should_decode_without_std() {
+        // only alloc is used here, so it also runs with `--no-default-features --features json`
+        let transport = MockTransport::new();
+        transport
+            .respond(
+                &format!("getTxByHash/{}", HASH),
+                200,
+                &tx_response("PACKED", "null"),
+            )
+            .respond(
+                &format!("getTxReceiptByTxHash/{}", HASH),
+                200,
+                &receipt("SUCCESS"),
+            );
+
+        let rpc = Rpc::with_transport(transport);
+        let response = rpc.get_tx_by_hash(HASH).unwrap();
+        assert_eq!(response.status, Status::PACKED);
+        assert_eq!(response.transaction.publisher, "admin");
+        assert!(response.transaction.tx_receipt.is_none());
+        let receipt = rpc.get_tx_receipt_by_tx_hash(HASH).unwrap();
+        assert_eq!(receipt.gas_usage, 2000.0);
+        assert_eq!(
+            rpc.transport().requests()[1].0,
+            format!("getTxReceiptByTxHash/{}", HASH)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    fn options(target: Status) -> ConfirmOptions {
+        ConfirmOptions {
+            target,
+            interval: std::time::Duration::from_millis(1),
+            timeout: std::time::Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn wait_for_tx_should_poll_until_packed() {
+        let transport = MockTransport::new();
+        let path = format!("getTxByHash/{}", HASH);
+        transport
+            .respond(&path, 400, r#"{"code": 1, "message": "tx not found"}"#)
+            .respond(&path, 200, &tx_response("PENDING", "null"))
+            .respond(&path, 200, &tx_response("PACKED", "null"));
+        transport.respond(
+            &format!("getTxReceiptByTxHash/{}", HASH),
+            200,
+            &receipt("SUCCESS"),
+        );
+
+        let rpc = Rpc::with_transport(transport);
+        let receipt = rpc
+            .wait_for_tx(HASH, i64::MAX, &options(Status::PACKED))
+            .unwrap();
+        assert_eq!(receipt.tx_hash, HASH);
+        assert_eq!(rpc.transport().requests().len(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn wait_for_tx_should_wait_for_irreversible() {
+        let transport = MockTransport::new();
+        let path = format!("getTxByHash/{}", HASH);
+        transport
+            .respond(&path, 200, &tx_response("PACKED", &receipt("SUCCESS")))
+            .respond(
+                &path,
+                200,
+                &tx_response("IRREVERSIBLE", &receipt("SUCCESS")),
+            );
+
+        let rpc = Rpc::with_transport(transport);
+        // an expired transaction that is packed is still waited for
+        assert!(rpc
+            .wait_for_tx(HASH, 0, &options(Status::IRREVERSIBLE))
+            .is_ok());
+        assert_eq!(rpc.transport().requests().len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn wait_for_tx_should_fail_with_status_code() {
+        let transport = MockTransport::new();
+        transport.respond(
+            &format!("getTxByHash/{}", HASH),
+            200,
+            &tx_response("PACKED", &receipt("BALANCE_NOT_ENOUGH")),
+        );
+
+        let rpc = Rpc::with_transport(transport);
+        match rpc.wait_for_tx(HASH, i64::MAX, &options(Status::PACKED)) {
+            Err(Error::TxExecError(code, _)) => assert_eq!(code, StatusCode::BALANCE_NOT_ENOUGH),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn wait_for_tx_should_return_other_node_errors() {
+        let transport = MockTransport::new();
+        transport
+            .respond(
+                &format!("getTxByHash/{}", HASH),
+                500,
+                r#"{"code": 2, "message": "illegal base58 data"}"#,
+            )
+            .respond(
+                &format!("getTxByHash/{}", HASH),
+                200,
+                &tx_response("PACKED", "null"),
+            );
+
+        let rpc = Rpc::with_transport(transport);
+        match rpc.wait_for_tx(HASH, i64::MAX, &options(Status::PACKED)) {
+            Err(Error::ErrorMessage(message)) => assert_eq!(message.message, "illegal base58 data"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(rpc.transport().requests().len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn wait_for_tx_should_stop_at_expiration_or_timeout() {
+        let transport = MockTransport::new();
+        transport.respond(
+            &format!("getTxByHash/{}", HASH),
+            200,
+            &tx_response("PENDING", "null"),
+        );
+        let rpc = Rpc::with_transport(transport);
+
+        match rpc.wait_for_tx(HASH, 0, &options(Status::PACKED)) {
+            Err(Error::TxExpired(hash)) => assert_eq!(hash, HASH),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let mut options = options(Status::PACKED);
+        options.timeout = std::time::Duration::from_millis(20);
+        match rpc.wait_for_tx(HASH, i64::MAX, &options) {
+            Err(Error::TxConfirmTimeout(hash)) => assert_eq!(hash, HASH),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
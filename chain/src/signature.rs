@@ -6,12 +6,14 @@ use alloc::vec::Vec;
 use crate::{Error, NumberBytes, Read, Write};
 use core::str::FromStr;
 use keys::algorithm;
+use keys::public::PublicKey;
+use keys::signer::Signer;
 use lite_json::{JsonObject, JsonValue, Serialize};
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize as SerSerialize, Serializer};
 
 #[derive(Clone, Default, Debug, NumberBytes, Write, Read)]
-#[cfg_attr(feature = "std", derive(SerSerialize))]
+#[cfg_attr(feature = "json", derive(SerSerialize))]
 #[iost_root_path = "crate"]
 pub struct Signature {
     /// Encryption algorithm. Currently only "ed25519" and "secp256k1" are supported
@@ -23,22 +25,63 @@ pub struct Signature {
 }
 
 impl Signature {
-    pub fn sign(message: &[u8], sign_algorithm: &str, sec_key: &[u8]) -> crate::Result<Signature> {
-        let algorithm = algorithm::new(sign_algorithm);
-        let pub_key = algorithm.get_pub_key(sec_key).unwrap();
-        let result = algorithm.sign(message, sec_key);
-        Ok(Signature {
-            algorithm: sign_algorithm.to_string(),
-            signature: base64::encode(result),
-            public_key: base64::encode(pub_key),
-        })
+    /// Signs the SHA3-256 `digest` of a transaction with `signer`
+    pub fn sign<S: Signer + ?Sized>(digest: &[u8; 32], signer: &S) -> crate::Result<Signature> {
+        let result = signer.sign_digest(digest).map_err(Error::Keys)?;
+        Ok(Signature::from_parts(&signer.public_key(), &result))
+    }
+
+    /// Signature made by the key `public_key`
+    pub fn from_parts(public_key: &PublicKey, signature: &[u8]) -> Signature {
+        Signature {
+            algorithm: public_key.algorithm().to_string(),
+            signature: base64::encode(signature),
+            public_key: base64::encode(public_key.as_bytes()),
+        }
     }
 
-    pub fn verify(&self, message: &[u8]) -> bool {
-        let algorithm = algorithm::new(self.algorithm.as_str());
-        let pub_key = base64::decode(self.public_key.as_str()).unwrap();
-        let sig = base64::decode(self.signature.as_str()).unwrap();
-        algorithm.verify(message, pub_key.as_slice(), sig.as_slice())
+    /// Public key and raw signature, e.g. to verify signatures in a batch
+    pub fn decode(&self) -> crate::Result<(PublicKey, Vec<u8>)> {
+        algorithm::new(self.algorithm.as_str()).map_err(Error::Keys)?;
+        let pub_key =
+            base64::decode(self.public_key.as_str()).map_err(|_| Error::InvalidSignature())?;
+        let sig = base64::decode(self.signature.as_str()).map_err(|_| Error::InvalidSignature())?;
+        let public_key =
+            PublicKey::from_bytes(self.algorithm.as_str(), &pub_key).map_err(Error::Keys)?;
+        Ok((public_key, sig))
+    }
+
+    /// Verifies the signature of `message`, failing with `Keys(VerifyFailed)` if it does not match
+    pub fn verify(&self, message: &[u8]) -> crate::Result<()> {
+        let algorithm = algorithm::new(self.algorithm.as_str()).map_err(Error::Keys)?;
+        let pub_key =
+            base64::decode(self.public_key.as_str()).map_err(|_| Error::InvalidSignature())?;
+        let sig = base64::decode(self.signature.as_str()).map_err(|_| Error::InvalidSignature())?;
+        algorithm
+            .verify(message, pub_key.as_slice(), sig.as_slice())
+            .map_err(Error::Keys)
+    }
+
+    /// Encodes the signature the way the chain does when hashing a transaction: the algorithm
+    /// byte, then the raw signature and public key, each prefixed with its length
+    pub fn to_bytes(&self) -> crate::Result<Vec<u8>> {
+        let algorithm: u8 = if self.algorithm.eq_ignore_ascii_case(algorithm::SECP256K1) {
+            1
+        } else if self.algorithm.eq_ignore_ascii_case(algorithm::ED25519) {
+            2
+        } else {
+            return Err(Error::InvalidSignature());
+        };
+        let sig = base64::decode(self.signature.as_str()).map_err(|_| Error::InvalidSignature())?;
+        let pub_key =
+            base64::decode(self.public_key.as_str()).map_err(|_| Error::InvalidSignature())?;
+
+        let mut bytes = vec![algorithm];
+        for item in [sig, pub_key].iter() {
+            bytes.extend_from_slice(&(item.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(item);
+        }
+        Ok(bytes)
     }
 
     pub fn no_std_serialize(&self) -> JsonValue {
@@ -61,7 +104,7 @@ impl Signature {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 impl<'de> serde::Deserialize<'de> for Signature {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use keys::public::PublicKey;
+use keys::signer::Signer;
+
+/// A signer answering asynchronously, e.g. over the network. Every `Signer` is one
+#[async_trait]
+pub trait AsyncSigner {
+    fn public_key(&self) -> PublicKey;
+
+    /// Signs the SHA3-256 `digest` of a transaction
+    async fn sign_digest(&self, digest: &[u8; 32]) -> keys::error::Result<Vec<u8>>;
+}
+
+#[async_trait]
+impl<S: Signer + Sync + ?Sized> AsyncSigner for S {
+    fn public_key(&self) -> PublicKey {
+        Signer::public_key(self)
+    }
+
+    async fn sign_digest(&self, digest: &[u8; 32]) -> keys::error::Result<Vec<u8>> {
+        Signer::sign_digest(self, digest)
+    }
+}
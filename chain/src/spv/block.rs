@@ -9,12 +9,14 @@ use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
 
 use keys::algorithm;
+use keys::batch::{verify_batch, BatchItem};
+use keys::error::Error::BatchVerifyFailed;
+use keys::public::PublicKey;
 
-use crate::spv::{Head, Sign, Tx};
-use crate::Error::IOSTBlockVerifyError;
+use crate::spv::{Head, Sign, Tx, TxReceipt};
+use crate::Error::{IOSTBlockVerifyError, IOSTInvalidBlockSignature, Keys};
 use crate::Result;
 use crate::SerializeData;
-use crate::TxReceipt;
 
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -42,27 +44,106 @@ pub struct Block {
 }
 
 impl Block {
+    /// Verifies the producer signatures of `blocks` in one batch, and that each block has a
+    /// receipt per transaction. Fails on the first wrong block.
     #[cfg(feature = "std")]
-    pub(crate) fn verify_self(&self) -> Result<()> {
-        let ed25519 = algorithm::new(algorithm::ED25519);
-        let sign = base64::decode(self.sign.sig.as_str()).unwrap();
-        let pub_key = bs58::decode(self.head.witness.as_str()).into_vec().unwrap();
-        let hash = self.head.hash();
+    pub fn verify_blocks(blocks: &[&Block]) -> Result<()> {
+        let mut decoded = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            let sign = base64::decode(block.sign.sig.as_str())
+                .map_err(|_| IOSTInvalidBlockSignature(block.head.number))?;
+            let pub_key = bs58::decode(block.head.witness.as_str())
+                .into_vec()
+                .map_err(|_| IOSTInvalidBlockSignature(block.head.number))?;
+            let pub_key = PublicKey::from_bytes(algorithm::ED25519, &pub_key).map_err(|_| {
+                IOSTBlockVerifyError(format!(
+                    "The witness of block {} is not a public key",
+                    block.head.number
+                ))
+            })?;
+            decoded.push((block.head.hash(), pub_key, sign));
+        }
+        let items: Vec<BatchItem> = decoded
+            .iter()
+            .map(|(hash, pub_key, sign)| (hash.as_slice(), *pub_key, sign.as_slice()))
+            .collect();
 
-        if !ed25519.verify(hash.as_slice(), pub_key.as_slice(), sign.as_slice()) {
-            return Err(IOSTBlockVerifyError(format!(
-                "The signature of block {} is wrong",
-                self.sign.sig
-            )));
+        match verify_batch(&items) {
+            Err(BatchVerifyFailed(index, _)) => {
+                return Err(IOSTInvalidBlockSignature(blocks[index].head.number));
+            }
+            result => result.map_err(Keys)?,
         }
 
-        if self.txs.len() != self.receipts.len() {
-            return Err(IOSTBlockVerifyError(format!(
-                "Tx len {} unmatch receipt len {}",
-                self.txs.len(),
-                self.receipts.len()
-            )));
+        for block in blocks {
+            if block.txs.len() != block.receipts.len() {
+                return Err(IOSTBlockVerifyError(format!(
+                    "Tx len {} unmatch receipt len {} in block {}",
+                    block.txs.len(),
+                    block.receipts.len(),
+                    block.head.number
+                )));
+            }
         }
         Ok(())
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+    use keys::keypair::KeyPair;
+
+    fn signed_block(number: i64, key_pair: &KeyPair) -> Block {
+        let head = Head {
+            version: 1,
+            parent_hash: vec![number as u8; 32],
+            tx_merkle_hash: vec![],
+            tx_receipt_merkle_hash: vec![],
+            info: vec![],
+            number,
+            witness: key_pair.public_base58(),
+            time: 1544709662543340000 + number * 500_000_000,
+        };
+        let sig = base64::encode(key_pair.sign(&head.hash()).unwrap());
+        Block {
+            head,
+            sign: Sign {
+                algorithm: 2,
+                sig,
+                pub_key: String::new(),
+            },
+            receipts: vec![],
+            txs: vec![],
+            tx_hashes: vec![],
+            receipt_hashes: vec![],
+            block_type: "NormalBlock".to_string(),
+        }
+    }
+
+    #[test]
+    fn verify_blocks_should_report_the_wrong_block() {
+        let key_pairs: Vec<KeyPair> = (0..3)
+            .map(|_| KeyPair::new(algorithm::ED25519).unwrap())
+            .collect();
+        let mut blocks: Vec<Block> = (0..13)
+            .map(|i| signed_block(1200 + i, &key_pairs[i as usize % 3]))
+            .collect();
+        assert!(Block::verify_blocks(&blocks.iter().collect::<Vec<_>>()).is_ok());
+
+        blocks[7].head.time += 1;
+        assert!(matches!(
+            Block::verify_blocks(&blocks.iter().collect::<Vec<_>>()),
+            Err(IOSTInvalidBlockSignature(1207))
+        ));
+
+        blocks[7] = signed_block(1207, &key_pairs[1]);
+        blocks[3].head.witness = "not base58!".to_string();
+        assert!(matches!(
+            Block::verify_blocks(&blocks.iter().collect::<Vec<_>>()),
+            Err(IOSTInvalidBlockSignature(1203))
+        ));
+    }
+}
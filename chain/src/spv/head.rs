@@ -2,7 +2,7 @@ use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
 use base64;
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 use serde::{Deserialize, Deserializer, Serialize};
 use sha3::{Digest, Sha3_256};
 
@@ -13,7 +13,7 @@ use crate::verify::BlockHead;
 use crate::{NumberBytes, Read, SerializeData, Write};
 
 #[derive(Debug, Clone, NumberBytes, SerializeData, Write, Read)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 #[iost_root_path = "crate"]
 pub struct Head {
     // #[cfg(feature = "std")]
@@ -59,10 +59,19 @@ impl Head {
     }
 
     pub fn verify(&self, sign: Sign) -> bool {
-        let ed25519 = algorithm::new(algorithm::ED25519);
-        let sign = base64::decode(sign.sig.as_str()).unwrap();
-        let pub_key = bs58::decode(self.witness.as_str()).into_vec().unwrap();
-        ed25519.verify(self.hash().as_slice(), pub_key.as_slice(), sign.as_slice())
+        let sign = match base64::decode(sign.sig.as_str()) {
+            Ok(sign) => sign,
+            Err(_) => return false,
+        };
+        let pub_key = match bs58::decode(self.witness.as_str()).into_vec() {
+            Ok(pub_key) => pub_key,
+            Err(_) => return false,
+        };
+        algorithm::new(algorithm::ED25519)
+            .and_then(|ed25519| {
+                ed25519.verify(self.hash().as_slice(), pub_key.as_slice(), sign.as_slice())
+            })
+            .is_ok()
     }
 }
 
@@ -83,7 +92,7 @@ pub fn from_block_head(bh: &BlockHead) -> Head {
     return head;
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 pub fn base64_de_string_to_bytes<'de, D>(de: D) -> Result<Vec<u8>, D::Error>
 where
     D: Deserializer<'de>,
@@ -93,12 +102,11 @@ where
     Ok(res.to_vec())
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 pub fn de_string_to_i64<'de, D>(de: D) -> Result<i64, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s: &str = Deserialize::deserialize(de)?;
-    let res = s.parse::<i64>().unwrap();
-    Ok(res)
+    s.parse::<i64>().map_err(serde::de::Error::custom)
 }
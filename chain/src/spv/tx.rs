@@ -1,3 +1,4 @@
+use alloc::collections::btree_map::BTreeMap;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
@@ -65,3 +66,31 @@ pub struct TxReceiptStatus {
     pub code: i32,
     pub message: String,
 }
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TxReceipt {
+    #[cfg(feature = "std")]
+    #[serde(rename = "txHash")]
+    pub tx_hash: String,
+    #[cfg(feature = "std")]
+    #[serde(rename = "gasUsage")]
+    pub gas_usage: String,
+    #[cfg(feature = "std")]
+    #[serde(rename = "ramUsage")]
+    pub ram_usage: BTreeMap<String, String>,
+    pub status: TxReceiptStatus,
+    pub returns: Vec<String>,
+    pub receipts: Vec<Receipt>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Receipt {
+    /// ABI function name
+    #[serde(rename = "funcName")]
+    #[cfg(feature = "std")]
+    pub func_name: String,
+    /// content
+    pub content: String,
+}
@@ -3,9 +3,7 @@ use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::{vec, vec::Vec};
 
-#[cfg(feature = "std")]
-use serde::{Deserialize, Serialize};
-
+use crate::vote_producer::ProducerStat;
 use crate::Error::*;
 use crate::Result;
 
@@ -16,17 +14,8 @@ pub struct Verify {
     epoch_producer: BTreeMap<i64, Vec<String>>,
 }
 
-#[derive(Debug, Default)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct WitnessStatus {
-    #[cfg(feature = "std")]
-    #[serde(rename = "pendingList")]
-    pub pending_list: Vec<String>,
-
-    #[cfg(feature = "std")]
-    #[serde(rename = "currentList")]
-    pub current_list: Vec<String>,
-}
+/// Producer lists of a `vote_producer.iost/stat` receipt, decoded by `ProducerStat`
+pub type WitnessStatus = ProducerStat;
 
 impl Verify {
     #[cfg(feature = "std")]
@@ -111,9 +100,8 @@ pub fn init(block: &Block) -> Result<Verify> {
 pub fn get_witness_status_from_block(block: &Block) -> Option<WitnessStatus> {
     for tx_receipt in block.receipts.iter() {
         for receipt in tx_receipt.receipts.iter() {
-            if receipt.func_name == "vote_producer.iost/stat" {
-                #[cfg(feature = "std")]
-                match serde_json::from_str(&receipt.content) {
+            if receipt.func_name == crate::vote_producer::STAT_RECEIPT {
+                match ProducerStat::from_content(&receipt.content) {
                     Ok(ws) => {
                         return Some(ws);
                     }
@@ -129,15 +117,10 @@ pub fn get_witness_status_from_block(block: &Block) -> Option<WitnessStatus> {
 
 #[cfg(feature = "std")]
 pub fn check_witness(v: &Verify, block: &Block, witness_blocks: Vec<Block>) -> Result<()> {
-    if let Err(_) = block.verify_self() {
-        return Err(IOSTInvalidBlockSignature());
-    }
-
-    for b in witness_blocks.iter() {
-        if let Err(_) = b.verify_self() {
-            return Err(IOSTInvalidBlockSignature());
-        }
-    }
+    let blocks: Vec<&Block> = core::iter::once(block)
+        .chain(witness_blocks.iter())
+        .collect();
+    Block::verify_blocks(&blocks)?;
 
     let block_number: i64 = block.head.number;
     let mut current_epoch_start_block: i64 = 0;
@@ -200,3 +183,96 @@ pub fn check_witness(v: &Verify, block: &Block, witness_blocks: Vec<Block>) -> R
         ))),
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use crate::spv::{Sign, TxReceipt, TxReceiptStatus};
+    use keys::algorithm;
+    use keys::keypair::KeyPair;
+
+    fn signed_block(number: i64, parent_hash: Vec<u8>, key_pair: &KeyPair) -> Block {
+        let head = Head {
+            version: 1,
+            parent_hash,
+            tx_merkle_hash: vec![],
+            tx_receipt_merkle_hash: vec![],
+            info: vec![],
+            number,
+            witness: key_pair.public_base58(),
+            time: 1544709662543340000 + number * 500_000_000,
+        };
+        let sig = base64::encode(key_pair.sign(&head.hash()).unwrap());
+        Block {
+            head,
+            sign: Sign {
+                algorithm: 2,
+                sig,
+                pub_key: String::new(),
+            },
+            receipts: vec![],
+            txs: vec![],
+            tx_hashes: vec![],
+            receipt_hashes: vec![],
+            block_type: "NormalBlock".to_string(),
+        }
+    }
+
+    /// Block 1201 followed by a chain of witness blocks, one per key pair
+    fn witnessed_block(key_pairs: &[KeyPair]) -> (Block, Vec<Block>) {
+        let block = signed_block(1201, vec![0; 32], &key_pairs[0]);
+        let mut witness_blocks: Vec<Block> = vec![];
+        let mut parent_hash = block.head.hash();
+        for (i, key_pair) in key_pairs.iter().enumerate() {
+            let witness = signed_block(1202 + i as i64, parent_hash, key_pair);
+            parent_hash = witness.head.hash();
+            witness_blocks.push(witness);
+        }
+        (block, witness_blocks)
+    }
+
+    #[test]
+    fn check_witness_should_report_the_wrong_block() {
+        let key_pairs: Vec<KeyPair> = (0..12)
+            .map(|_| KeyPair::new(algorithm::ED25519).unwrap())
+            .collect();
+        let mut v = Verify::default();
+        v.epoch_producer
+            .insert(1200, key_pairs.iter().map(|k| k.public_base58()).collect());
+
+        let (block, witness_blocks) = witnessed_block(&key_pairs);
+        assert!(check_witness(&v, &block, witness_blocks).is_ok());
+
+        let (block, mut witness_blocks) = witnessed_block(&key_pairs);
+        witness_blocks[5].sign.sig = witness_blocks[6].sign.sig.clone();
+        assert!(matches!(
+            check_witness(&v, &block, witness_blocks),
+            Err(IOSTInvalidBlockSignature(1207))
+        ));
+
+        let (block, mut witness_blocks) = witnessed_block(&key_pairs);
+        witness_blocks[2].receipts.push(TxReceipt {
+            tx_hash: String::new(),
+            gas_usage: String::new(),
+            ram_usage: BTreeMap::new(),
+            status: TxReceiptStatus {
+                code: 0,
+                message: String::new(),
+            },
+            returns: vec![],
+            receipts: vec![],
+        });
+        match check_witness(&v, &block, witness_blocks) {
+            Err(IOSTBlockVerifyError(message)) => {
+                assert_eq!(message, "Tx len 0 unmatch receipt len 1 in block 1204")
+            }
+            result => panic!("unexpected {:?}", result),
+        }
+
+        let (block, witness_blocks) = witnessed_block(&key_pairs[..11]);
+        assert!(matches!(
+            check_witness(&v, &block, witness_blocks),
+            Err(IOSTBlockWitnessError(_))
+        ));
+    }
+}
@@ -0,0 +1,260 @@
+//! Typed actions of the `system.iost` contract, which deploys and updates contracts
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use lite_json::{parse_json, JsonValue};
+
+use crate::contract_action::{arg_string, json_strings, parse_args, string_arg};
+use crate::Error::{JsonParserError, TxExecError, UnexpectedAction};
+use crate::{Contract, ContractAction, ContractId, IostAction, StatusCode, TxReceipt};
+
+/// Id of the system contract
+pub const SYSTEM_CONTRACT: &str = "system.iost";
+
+fn arg_contract(args: &[JsonValue], index: usize) -> crate::Result<Contract> {
+    Contract::from_json(&arg_string(args, index)?)
+}
+
+/// Deployment of a new contract, whose id is read from the receipt with `deployed_contract_id`
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetCode {
+    pub contract: Contract,
+}
+
+impl ContractAction for SetCode {
+    const CONTRACT: &'static str = SYSTEM_CONTRACT;
+    const ACTION_NAME: &'static str = "setCode";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![string_arg(&self.contract.to_json())]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(SetCode {
+            contract: arg_contract(args, 0)?,
+        })
+    }
+}
+
+/// Update of the deployed contract of id `contract.id`, passing `data` to its `can_update`
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateCode {
+    pub contract: Contract,
+    pub data: String,
+}
+
+impl ContractAction for UpdateCode {
+    const CONTRACT: &'static str = SYSTEM_CONTRACT;
+    const ACTION_NAME: &'static str = "updateCode";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![string_arg(&self.contract.to_json()), string_arg(&self.data)]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(UpdateCode {
+            contract: arg_contract(args, 0)?,
+            data: arg_string(args, 1)?,
+        })
+    }
+}
+
+/// Cancellation of the delayed transaction of hash `hash`, in base58
+#[derive(Clone, Debug, PartialEq)]
+pub struct CancelDelaytx {
+    pub hash: String,
+}
+
+impl ContractAction for CancelDelaytx {
+    const CONTRACT: &'static str = SYSTEM_CONTRACT;
+    const ACTION_NAME: &'static str = "cancelDelaytx";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![string_arg(&self.hash)]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(CancelDelaytx {
+            hash: arg_string(args, 0)?,
+        })
+    }
+}
+
+/// Any decoded action of the system contract
+#[derive(Clone, Debug, PartialEq)]
+pub enum SystemAction {
+    SetCode(SetCode),
+    UpdateCode(UpdateCode),
+    CancelDelaytx(CancelDelaytx),
+}
+
+impl SystemAction {
+    pub fn from_action(action: &IostAction) -> crate::Result<Self> {
+        let action_name = String::from_utf8_lossy(&action.action_name);
+        let unexpected = || UnexpectedAction(format!("{}/{}", action.contract, action_name));
+        if action.contract != SYSTEM_CONTRACT {
+            return Err(unexpected());
+        }
+        let args = parse_args(&action.data)?;
+        Ok(match action_name.as_ref() {
+            SetCode::ACTION_NAME => SystemAction::SetCode(SetCode::from_args(&args)?),
+            UpdateCode::ACTION_NAME => SystemAction::UpdateCode(UpdateCode::from_args(&args)?),
+            CancelDelaytx::ACTION_NAME => {
+                SystemAction::CancelDelaytx(CancelDelaytx::from_args(&args)?)
+            }
+            _ => return Err(unexpected()),
+        })
+    }
+}
+
+/// Id of the contract deployed by a transaction whose first action is a `setCode`, returned by
+/// that action as `["Contract..."]`
+pub fn deployed_contract_id(receipt: &TxReceipt) -> crate::Result<ContractId> {
+    if receipt.status_code != StatusCode::SUCCESS {
+        return Err(TxExecError(
+            receipt.status_code.clone(),
+            receipt.message.clone(),
+        ));
+    }
+    let returns = receipt.returns.first().ok_or_else(JsonParserError)?;
+    let returns = parse_json(returns).map_err(|_| JsonParserError())?;
+    match json_strings(Some(&returns))?.first() {
+        Some(id) => id.parse(),
+        None => Err(JsonParserError()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::collections::BTreeMap;
+    use alloc::string::ToString;
+
+    use crate::{AmountLimit, ABI};
+
+    /// Data of a `setCode` action in the form of the chain's Go encoding, which omits the empty
+    /// `ID` and `amountLimit`; not captured from a node
+    const SET_CODE_DATA: &str = r#"["{\"info\":{\"lang\":\"javascript\",\"version\":\"1.0.0\",\"abi\":[{\"name\":\"hello\",\"args\":[\"string\"]},{\"name\":\"pay\",\"args\":[\"string\",\"string\"],\"amountLimit\":[{\"token\":\"iost\",\"val\":\"10\"}]}]},\"code\":\"class Test {\\n  init() {}\\n  hello(name) { return \\\"hello \\\" + name; }\\n}\\nmodule.exports = Test;\"}"]"#;
+
+    /// `returns` of the receipt of a `setCode` transaction
+    const SET_CODE_RETURNS: &str = r#"["ContractCvwZq8SJPqygvSu5SSVNsuWqMGTDyKBPr6Gd74crJiiR"]"#;
+
+    fn contract() -> Contract {
+        Contract::javascript(
+            "class Test {\n  init() {}\n  hello(name) { return \"hello \" + name; }\n}\nmodule.exports = Test;",
+            "1.0.0",
+            vec![
+                ABI {
+                    name: "hello".to_string(),
+                    args: vec!["string".to_string()],
+                    amount_limit: vec![],
+                },
+                ABI {
+                    name: "pay".to_string(),
+                    args: vec!["string".to_string(), "string".to_string()],
+                    amount_limit: vec![AmountLimit::new(
+                        "iost".parse().unwrap(),
+                        "10".parse().unwrap(),
+                    )],
+                },
+            ],
+        )
+    }
+
+    fn receipt(status_code: StatusCode, returns: &str) -> TxReceipt {
+        TxReceipt {
+            tx_hash: String::new(),
+            gas_usage: 0.0,
+            ram_usage: BTreeMap::new(),
+            status_code,
+            message: String::new(),
+            returns: vec![returns.to_string()],
+            receipts: vec![],
+        }
+    }
+
+    #[test]
+    fn set_code_should_round_trip_fixture() {
+        let set_code = SetCode {
+            contract: contract(),
+        };
+        let action = set_code.to_action().unwrap();
+        assert_eq!(action.contract, SYSTEM_CONTRACT);
+        assert_eq!(action.action_name, b"setCode");
+        assert_eq!(action.data, SET_CODE_DATA.as_bytes());
+
+        let recorded = IostAction {
+            contract: SYSTEM_CONTRACT.parse().unwrap(),
+            action_name: b"setCode".to_vec(),
+            data: SET_CODE_DATA.as_bytes().to_vec(),
+        };
+        assert_eq!(
+            SystemAction::from_action(&recorded).unwrap(),
+            SystemAction::SetCode(set_code)
+        );
+
+        let minimal = Contract::from_json(
+            r#"{"info":{"lang":"javascript","version":"1.0.0","abi":[{"name":"init"}]},"code":""}"#,
+        )
+        .unwrap();
+        assert_eq!(minimal.id, "");
+        assert!(minimal.abis[0].args.is_empty());
+        assert!(Contract::from_json(r#"{"code":""}"#).is_err());
+
+        // Go's encoding/json escapes `<`, `>` and `&` of the code
+        let escaped = Contract::from_json(
+            r#"{"info":{"lang":"javascript","version":"1.0.0"},"code":"a \u003c b \u0026\u0026 b \u003e c"}"#,
+        )
+        .unwrap();
+        assert_eq!(escaped.code, "a < b && b > c");
+        assert!(escaped.abis.is_empty());
+    }
+
+    #[test]
+    fn update_code_and_cancel_delaytx_should_round_trip() {
+        let mut contract = contract();
+        contract.id = "ContractCvwZq8SJPqygvSu5SSVNsuWqMGTDyKBPr6Gd74crJiiR".to_string();
+        let update_code = UpdateCode {
+            contract,
+            data: String::new(),
+        };
+        let action = update_code.to_action().unwrap();
+        assert!(update_code
+            .contract
+            .to_json()
+            .starts_with(r#"{"ID":"ContractCvwZq8SJPqygvSu5SSVNsuWqMGTDyKBPr6Gd74crJiiR","info""#));
+        assert_eq!(UpdateCode::from_action(&action).unwrap(), update_code);
+        assert!(SetCode::from_action(&action).is_err());
+
+        let cancel = CancelDelaytx {
+            hash: "8MGFWLoE5vkLCHp4oazbdGgqHo4n3G6JPb7aj9BuaLYU".to_string(),
+        };
+        let action = cancel.to_action().unwrap();
+        assert_eq!(
+            action.data,
+            br#"["8MGFWLoE5vkLCHp4oazbdGgqHo4n3G6JPb7aj9BuaLYU"]"#.to_vec()
+        );
+        assert_eq!(
+            SystemAction::from_action(&action).unwrap(),
+            SystemAction::CancelDelaytx(cancel)
+        );
+    }
+
+    #[test]
+    fn deployed_contract_id_should_read_receipt() {
+        let id = deployed_contract_id(&receipt(StatusCode::SUCCESS, SET_CODE_RETURNS)).unwrap();
+        assert_eq!(
+            id.as_str(),
+            "ContractCvwZq8SJPqygvSu5SSVNsuWqMGTDyKBPr6Gd74crJiiR"
+        );
+        assert!(!id.is_system());
+        assert!(matches!(
+            deployed_contract_id(&receipt(StatusCode::RUNTIME_ERROR, "")),
+            Err(TxExecError(StatusCode::RUNTIME_ERROR, _))
+        ));
+        assert!(deployed_contract_id(&receipt(StatusCode::SUCCESS, "[]")).is_err());
+    }
+}
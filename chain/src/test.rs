@@ -1,5 +1,7 @@
 use crate::time_point::TimePoint;
-use crate::{AmountLimit, IostAction, NumberBytes, Read, Result, SerializeData, Tx, Write};
+use crate::{
+    AccountName, AmountLimit, IostAction, NumberBytes, Read, Result, SerializeData, Tx, Write,
+};
 use alloc::vec::Vec;
 use alloc::{format, vec};
 // use base58::{FromBase58, ToBase58};
@@ -24,23 +26,21 @@ fn print_hex() {
 #[test]
 fn should_action_serialization_successful() {
     let action = IostAction::new(
-        String::from("'token.iost'"),
+        String::from("token.iost"),
         String::from("'transfer'"),
         String::from(r#"["iost","admin","lispczz","10.12034123",""]"#),
-    );
+    )
+    .unwrap();
 
     let result: Result<Vec<u8>> = action.to_serialize_data();
     assert!(result.is_ok());
     let data = result.unwrap();
-    assert_eq!(data.len(), 77);
+    assert_eq!(data.len(), 75);
 }
 
 #[test]
 fn should_amount_limit_serialization_successful() {
-    let amount_limit = AmountLimit {
-        token: "*".to_string(),
-        value: "unlimited".to_string(),
-    };
+    let amount_limit = AmountLimit::unlimited();
     let result: Result<Vec<u8>> = amount_limit.to_serialize_data();
     assert!(result.is_ok());
     let data = result.unwrap();
@@ -52,15 +52,13 @@ use sha3::{Digest, Sha3_256};
 #[test]
 fn should_tx_serialization_and_sign_successful() {
     let action = IostAction::new(
-        String::from("'token.iost'"),
+        String::from("token.iost"),
         String::from("'transfer'"),
         String::from(r#"["iost","admin","lispczz","10.12034123",""]"#),
-    );
+    )
+    .unwrap();
 
-    let amount_limit = AmountLimit {
-        token: "*".to_string(),
-        value: "unlimited".to_string(),
-    };
+    let amount_limit = AmountLimit::unlimited();
     let time = Utc::now().timestamp_nanos();
     let expiration = time + Duration::seconds(10000).num_nanoseconds().unwrap();
 
@@ -73,7 +71,7 @@ fn should_tx_serialization_and_sign_successful() {
         chain_id: 1024,
         actions: vec![action.clone()],
         amount_limit: vec![amount_limit],
-        publisher: "".to_string(),
+        publisher: AccountName::default(),
         publisher_sigs: vec![],
         signers: vec![],
         signatures: vec![],
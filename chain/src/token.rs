@@ -0,0 +1,498 @@
+//! Typed actions of the `token.iost` contract, which manages the `iost` token and the tokens
+//! created by users
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use lite_json::JsonValue;
+
+use crate::contract_action::{
+    arg_i64, arg_parse, arg_string, field, number_arg, parse_args, string_arg,
+};
+use crate::Error::{JsonParserError, UnexpectedAction};
+use crate::{AccountName, Amount, ContractAction, IostAction};
+
+/// Id of the token contract
+pub const TOKEN_CONTRACT: &str = "token.iost";
+
+/// Transfer of `amount` of `token` from the account or contract `from` to `to`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transfer {
+    pub token: String,
+    pub from: String,
+    pub to: String,
+    pub amount: Amount,
+    pub memo: String,
+}
+
+impl ContractAction for Transfer {
+    const CONTRACT: &'static str = TOKEN_CONTRACT;
+    const ACTION_NAME: &'static str = "transfer";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.token),
+            string_arg(&self.from),
+            string_arg(&self.to),
+            string_arg(&self.amount),
+            string_arg(&self.memo),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(Transfer {
+            token: arg_string(args, 0)?,
+            from: arg_string(args, 1)?,
+            to: arg_string(args, 2)?,
+            amount: arg_parse(args, 3)?,
+            memo: arg_string(args, 4)?,
+        })
+    }
+}
+
+/// Transfer of `amount` of `token` which stays frozen on `to` until `unfreeze_time`, in
+/// nanoseconds since the Unix epoch
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransferFreeze {
+    pub token: String,
+    pub from: String,
+    pub to: String,
+    pub amount: Amount,
+    pub unfreeze_time: i64,
+    pub memo: String,
+}
+
+impl ContractAction for TransferFreeze {
+    const CONTRACT: &'static str = TOKEN_CONTRACT;
+    const ACTION_NAME: &'static str = "transferFreeze";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.token),
+            string_arg(&self.from),
+            string_arg(&self.to),
+            string_arg(&self.amount),
+            number_arg(self.unfreeze_time),
+            string_arg(&self.memo),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(TransferFreeze {
+            token: arg_string(args, 0)?,
+            from: arg_string(args, 1)?,
+            to: arg_string(args, 2)?,
+            amount: arg_parse(args, 3)?,
+            unfreeze_time: arg_i64(args, 4)?,
+            memo: arg_string(args, 5)?,
+        })
+    }
+}
+
+/// Settings of a token given at its creation
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenConfig {
+    /// decimal places of the amounts of the token
+    pub decimal: u32,
+    pub can_transfer: bool,
+    pub only_issuer_can_transfer: bool,
+    pub full_name: String,
+}
+
+impl Default for TokenConfig {
+    /// The defaults of the chain
+    fn default() -> Self {
+        TokenConfig {
+            decimal: 8,
+            can_transfer: true,
+            only_issuer_can_transfer: false,
+            full_name: String::new(),
+        }
+    }
+}
+
+impl TokenConfig {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Object(vec![
+            ("decimal".chars().collect(), number_arg(self.decimal as i64)),
+            (
+                "canTransfer".chars().collect(),
+                JsonValue::Boolean(self.can_transfer),
+            ),
+            (
+                "onlyIssuerCanTransfer".chars().collect(),
+                JsonValue::Boolean(self.only_issuer_can_transfer),
+            ),
+            ("fullName".chars().collect(), string_arg(&self.full_name)),
+        ])
+    }
+
+    /// Reads the settings given in `config`, the others keep the defaults of the chain
+    fn from_json(config: &JsonValue) -> crate::Result<Self> {
+        if !matches!(config, JsonValue::Object(_)) {
+            return Err(JsonParserError());
+        }
+        let mut token_config = TokenConfig::default();
+        if let Some(decimal) = field(config, "decimal") {
+            token_config.decimal = match decimal {
+                JsonValue::Number(number)
+                    if number.fraction_length == 0 && number.exponent == 0 =>
+                {
+                    u32::try_from(number.integer).map_err(|_| JsonParserError())?
+                }
+                _ => return Err(JsonParserError()),
+            };
+        }
+        for (key, value) in [
+            ("canTransfer", &mut token_config.can_transfer),
+            (
+                "onlyIssuerCanTransfer",
+                &mut token_config.only_issuer_can_transfer,
+            ),
+        ] {
+            match field(config, key) {
+                Some(JsonValue::Boolean(flag)) => *value = *flag,
+                Some(_) => return Err(JsonParserError()),
+                None => {}
+            }
+        }
+        match field(config, "fullName") {
+            Some(JsonValue::String(chars)) => token_config.full_name = chars.iter().collect(),
+            Some(_) => return Err(JsonParserError()),
+            None => {}
+        }
+        Ok(token_config)
+    }
+}
+
+/// Creation of the token `token`, which `issuer` can issue up to `total_supply`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Create {
+    pub token: String,
+    pub issuer: AccountName,
+    pub total_supply: i64,
+    pub config: TokenConfig,
+}
+
+impl ContractAction for Create {
+    const CONTRACT: &'static str = TOKEN_CONTRACT;
+    const ACTION_NAME: &'static str = "create";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.token),
+            string_arg(&self.issuer),
+            number_arg(self.total_supply),
+            self.config.to_json(),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(Create {
+            token: arg_string(args, 0)?,
+            issuer: arg_parse(args, 1)?,
+            total_supply: arg_i64(args, 2)?,
+            config: TokenConfig::from_json(args.get(3).ok_or(JsonParserError())?)?,
+        })
+    }
+}
+
+/// Issue of `amount` of `token` to `to`, by the issuer of the token
+#[derive(Clone, Debug, PartialEq)]
+pub struct Issue {
+    pub token: String,
+    pub to: String,
+    pub amount: Amount,
+}
+
+impl ContractAction for Issue {
+    const CONTRACT: &'static str = TOKEN_CONTRACT;
+    const ACTION_NAME: &'static str = "issue";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.token),
+            string_arg(&self.to),
+            string_arg(&self.amount),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(Issue {
+            token: arg_string(args, 0)?,
+            to: arg_string(args, 1)?,
+            amount: arg_parse(args, 2)?,
+        })
+    }
+}
+
+/// Destruction of `amount` of `token` owned by `from`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Destroy {
+    pub token: String,
+    pub from: String,
+    pub amount: Amount,
+}
+
+impl ContractAction for Destroy {
+    const CONTRACT: &'static str = TOKEN_CONTRACT;
+    const ACTION_NAME: &'static str = "destroy";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.token),
+            string_arg(&self.from),
+            string_arg(&self.amount),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(Destroy {
+            token: arg_string(args, 0)?,
+            from: arg_string(args, 1)?,
+            amount: arg_parse(args, 2)?,
+        })
+    }
+}
+
+/// Query of the balance of `token` of `account`, returned in the receipt
+#[derive(Clone, Debug, PartialEq)]
+pub struct BalanceOf {
+    pub token: String,
+    pub account: String,
+}
+
+impl ContractAction for BalanceOf {
+    const CONTRACT: &'static str = TOKEN_CONTRACT;
+    const ACTION_NAME: &'static str = "balanceOf";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![string_arg(&self.token), string_arg(&self.account)]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(BalanceOf {
+            token: arg_string(args, 0)?,
+            account: arg_string(args, 1)?,
+        })
+    }
+}
+
+/// Query of the issued supply of `token`, returned in the receipt
+#[derive(Clone, Debug, PartialEq)]
+pub struct Supply {
+    pub token: String,
+}
+
+impl ContractAction for Supply {
+    const CONTRACT: &'static str = TOKEN_CONTRACT;
+    const ACTION_NAME: &'static str = "supply";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![string_arg(&self.token)]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(Supply {
+            token: arg_string(args, 0)?,
+        })
+    }
+}
+
+/// Any decoded action of the token contract
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenAction {
+    Transfer(Transfer),
+    TransferFreeze(TransferFreeze),
+    Create(Create),
+    Issue(Issue),
+    Destroy(Destroy),
+    BalanceOf(BalanceOf),
+    Supply(Supply),
+}
+
+impl TokenAction {
+    pub fn from_action(action: &IostAction) -> crate::Result<Self> {
+        let action_name = String::from_utf8_lossy(&action.action_name);
+        if action.contract != TOKEN_CONTRACT {
+            return Err(UnexpectedAction(format!(
+                "{}/{}",
+                action.contract, action_name
+            )));
+        }
+        let args = parse_args(&action.data)?;
+        Ok(match action_name.as_ref() {
+            Transfer::ACTION_NAME => TokenAction::Transfer(Transfer::from_args(&args)?),
+            TransferFreeze::ACTION_NAME => {
+                TokenAction::TransferFreeze(TransferFreeze::from_args(&args)?)
+            }
+            Create::ACTION_NAME => TokenAction::Create(Create::from_args(&args)?),
+            Issue::ACTION_NAME => TokenAction::Issue(Issue::from_args(&args)?),
+            Destroy::ACTION_NAME => TokenAction::Destroy(Destroy::from_args(&args)?),
+            BalanceOf::ACTION_NAME => TokenAction::BalanceOf(BalanceOf::from_args(&args)?),
+            Supply::ACTION_NAME => TokenAction::Supply(Supply::from_args(&args)?),
+            _ => {
+                return Err(UnexpectedAction(format!(
+                    "{}/{}",
+                    action.contract, action_name
+                )))
+            }
+        })
+    }
+}
+
+/// Transfer of `amount` of `token`
+pub fn transfer<T: AsRef<str>>(
+    token: T,
+    from: T,
+    to: T,
+    amount: T,
+    memo: T,
+) -> crate::Result<IostAction> {
+    Transfer {
+        token: token.as_ref().to_string(),
+        from: from.as_ref().to_string(),
+        to: to.as_ref().to_string(),
+        amount: amount.as_ref().parse()?,
+        memo: memo.as_ref().to_string(),
+    }
+    .to_action()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn data(action: &IostAction) -> &str {
+        core::str::from_utf8(&action.data).unwrap()
+    }
+
+    #[test]
+    fn token_actions_should_be_encoded_as_abi() {
+        let action = transfer("ABC", "admin", "lispczz3", "10.50", "hi \"there\"").unwrap();
+        assert_eq!(action.contract, TOKEN_CONTRACT);
+        assert_eq!(action.action_name, b"transfer");
+        assert_eq!(
+            data(&action),
+            r#"["ABC","admin","lispczz3","10.50","hi \"there\""]"#
+        );
+
+        let freeze = TransferFreeze {
+            token: "iost".to_string(),
+            from: "admin".to_string(),
+            to: "lispczz3".to_string(),
+            amount: "1".parse().unwrap(),
+            unfreeze_time: 1598918348274417000,
+            memo: "".to_string(),
+        };
+        assert_eq!(
+            data(&freeze.to_action().unwrap()),
+            r#"["iost","admin","lispczz3","1",1598918348274417000,""]"#
+        );
+
+        let create = Create {
+            token: "abc".to_string(),
+            issuer: "admin".parse().unwrap(),
+            total_supply: 21000000,
+            config: TokenConfig {
+                decimal: 2,
+                full_name: "ABC coin".to_string(),
+                ..TokenConfig::default()
+            },
+        };
+        assert_eq!(
+            data(&create.to_action().unwrap()),
+            r#"["abc","admin",21000000,{"decimal":2,"canTransfer":true,"onlyIssuerCanTransfer":false,"fullName":"ABC coin"}]"#
+        );
+
+        let issue = Issue {
+            token: "abc".to_string(),
+            to: "lispczz3".to_string(),
+            amount: "100".parse().unwrap(),
+        };
+        assert_eq!(
+            data(&issue.to_action().unwrap()),
+            r#"["abc","lispczz3","100"]"#
+        );
+        let balance_of = BalanceOf {
+            token: "abc".to_string(),
+            account: "lispczz3".to_string(),
+        };
+        assert_eq!(
+            data(&balance_of.to_action().unwrap()),
+            r#"["abc","lispczz3"]"#
+        );
+        let supply = Supply {
+            token: "abc".to_string(),
+        };
+        assert_eq!(supply.to_action().unwrap().action_name, b"supply");
+    }
+
+    #[test]
+    fn token_actions_should_round_trip() {
+        let create = Create {
+            token: "abc".to_string(),
+            issuer: "admin".parse().unwrap(),
+            total_supply: 21000000,
+            config: TokenConfig::default(),
+        };
+        let destroy = Destroy {
+            token: "abc".to_string(),
+            from: "lispczz3".to_string(),
+            amount: "0.5".parse().unwrap(),
+        };
+        assert_eq!(
+            Create::from_action(&create.to_action().unwrap()).unwrap(),
+            create
+        );
+        assert_eq!(
+            TokenAction::from_action(&destroy.to_action().unwrap()).unwrap(),
+            TokenAction::Destroy(destroy.clone())
+        );
+
+        // settings missing from the config keep the defaults of the chain
+        let action = IostAction::new(
+            TOKEN_CONTRACT.to_string(),
+            "create".to_string(),
+            r#"["abc", "admin", 100, {"fullName": "ABC"}]"#.to_string(),
+        )
+        .unwrap();
+        let decoded = Create::from_action(&action).unwrap();
+        assert_eq!(decoded.config.decimal, 8);
+        assert_eq!(decoded.config.full_name, "ABC");
+        for config in [
+            r#"{"decimal": -1}"#,
+            r#"{"decimal": 4294967296}"#,
+            r#"{"decimal": 8e1}"#,
+        ] {
+            let action = IostAction::new(
+                TOKEN_CONTRACT.to_string(),
+                "create".to_string(),
+                format!(r#"["abc", "admin", 100, {}]"#, config),
+            )
+            .unwrap();
+            assert!(matches!(
+                Create::from_action(&action),
+                Err(JsonParserError())
+            ));
+        }
+
+        assert!(matches!(
+            Transfer::from_action(&destroy.to_action().unwrap()),
+            Err(UnexpectedAction(ref name)) if name == "token.iost/destroy"
+        ));
+        let action = IostAction::new(
+            TOKEN_CONTRACT.to_string(),
+            "transfer".to_string(),
+            r#"["iost", "admin", "lispczz3", 10, ""]"#.to_string(),
+        )
+        .unwrap();
+        assert!(matches!(
+            Transfer::from_action(&action),
+            Err(JsonParserError())
+        ));
+    }
+}
@@ -0,0 +1,14 @@
+use alloc::vec::Vec;
+
+use crate::FrozenBalance;
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct TokenBalance {
+    /// balance of the token
+    pub balance: f64,
+    /// information on frozen balances of the token
+    pub frozen_balances: Vec<FrozenBalance>,
+}
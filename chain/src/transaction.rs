@@ -1,26 +1,40 @@
 use alloc::string::String;
 use alloc::vec::Vec;
 
-use crate::{
-    AmountLimit, IostAction, NumberBytes, Read, ReadError, Signature, TxReceipt, Write, WriteError,
-};
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
+use crate::spv::de_string_to_i64;
+use crate::{AmountLimit, IostAction, Signature, Status, TxReceipt};
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct TransactionResponse {
+    /// PENDING - transaction is cached; PACKED - transaction is in a non-irreversible block; IRREVERSIBLE - transaction is in an irreversible block
+    pub status: Status,
+    /// the transaction
+    pub transaction: Transaction,
+    /// number of the block which contains the transaction
+    pub block_number: String,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct Transaction {
     /// transaction's hash
     pub hash: String,
     /// timestamp of the transaction
+    #[cfg_attr(feature = "json", serde(deserialize_with = "de_string_to_i64"))]
     pub time: i64,
     /// the expiration of the transaction
+    #[cfg_attr(feature = "json", serde(deserialize_with = "de_string_to_i64"))]
     pub expiration: i64,
     /// GAS ratio, we recommend it to be 1.00 (1.00 – 100.00). Raise the ratio to let the network pack it faster
     pub gas_ratio: f64,
     /// Upper limits of GAS. This transaction will never cost more GAS than this amount
     pub gas_limit: f64,
     /// Transactions will be delayed by this much, in nanosecond
+    #[cfg_attr(feature = "json", serde(deserialize_with = "de_string_to_i64"))]
     pub delay: i64,
     /// id of blockchain on which the transaction could be executed
     pub chain_id: i32,
@@ -35,7 +49,8 @@ pub struct Transaction {
     /// Users may specify token limits. For example, {"iost": 100} specifies each signers will not spend more than 100 IOST for the transaction
     pub amount_limit: Vec<AmountLimit>,
     /// The signatures of signers
+    #[cfg_attr(feature = "json", serde(default))]
     pub signatures: Vec<Signature>,
-    // the receipt of the transaction Action
-    // pub tx_receipt: TxReceipt,
+    /// the receipt of the transaction Action
+    pub tx_receipt: Option<TxReceipt>,
 }
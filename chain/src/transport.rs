@@ -0,0 +1,108 @@
+use alloc::collections::{btree_map::BTreeMap, VecDeque};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+#[cfg(feature = "std")]
+use async_trait::async_trait;
+
+use crate::Error::RpcTransportError;
+use crate::Result;
+
+/// Raw response of the IOST HTTP API
+#[derive(Clone, Debug, PartialEq)]
+pub struct RpcResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// response body, JSON encoded
+    pub body: Vec<u8>,
+}
+
+/// Transport carrying requests to the HTTP API of an IOST node.
+///
+/// Implement it to reach a node through any HTTP stack, e.g. the offchain worker http of substrate.
+pub trait RpcTransport {
+    /// Requests `path` of the node, e.g. `getChainInfo` or `getTxByHash/{hash}`. It is a GET when
+    /// `body` is `None`, otherwise the JSON `body` is POSTed.
+    fn request(&self, path: &str, body: Option<&[u8]>) -> Result<RpcResponse>;
+}
+
+/// Asynchronous flavour of [`RpcTransport`], carrying the requests of the async client
+#[cfg(feature = "std")]
+#[async_trait]
+pub trait AsyncRpcTransport {
+    /// Requests `path` of the node, see [`RpcTransport::request`]
+    async fn request(&self, path: &str, body: Option<&[u8]>) -> Result<RpcResponse>;
+}
+
+/// In-memory transport answering scripted responses, for tests.
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    responses: RefCell<BTreeMap<String, VecDeque<RpcResponse>>>,
+    requests: RefCell<Vec<(String, Option<Vec<u8>>)>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a response for `path`. Responses of the same path are answered in order, and the
+    /// last one is repeated once the queue is drained.
+    pub fn respond(&self, path: &str, status: u16, body: &str) -> &Self {
+        self.responses
+            .borrow_mut()
+            .entry(path.to_string())
+            .or_default()
+            .push_back(RpcResponse {
+                status,
+                body: body.as_bytes().to_vec(),
+            });
+        self
+    }
+
+    /// Requests received so far, as `(path, body)`
+    pub fn requests(&self) -> Vec<(String, Option<Vec<u8>>)> {
+        self.requests.borrow().clone()
+    }
+}
+
+impl RpcTransport for MockTransport {
+    fn request(&self, path: &str, body: Option<&[u8]>) -> Result<RpcResponse> {
+        self.requests
+            .borrow_mut()
+            .push((path.to_string(), body.map(|b| b.to_vec())));
+        let mut responses = self.responses.borrow_mut();
+        match responses.get_mut(path) {
+            Some(queue) if queue.len() > 1 => Ok(queue.pop_front().unwrap()),
+            Some(queue) if queue.len() == 1 => Ok(queue[0].clone()),
+            _ => Err(RpcTransportError(format!("no response for {}", path))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mock_transport_should_answer_in_order() {
+        let transport = MockTransport::new();
+        transport.respond("getTxByHash/abc", 400, "first").respond(
+            "getTxByHash/abc",
+            200,
+            "second",
+        );
+
+        let first = transport.request("getTxByHash/abc", None).unwrap();
+        assert_eq!(first.status, 400);
+        assert_eq!(first.body, b"first".to_vec());
+        for _ in 0..2 {
+            let last = transport.request("getTxByHash/abc", None).unwrap();
+            assert_eq!(last.body, b"second".to_vec());
+        }
+        assert!(transport.request("getChainInfo", None).is_err());
+        assert_eq!(transport.requests().len(), 4);
+    }
+}
@@ -2,20 +2,26 @@ use alloc::string::{String, ToString};
 use alloc::vec;
 use alloc::vec::Vec;
 
-use crate::Error::{BytesReadError, InvalidPublisherSignature, InvalidSignature};
+use crate::Error::{BytesReadError, PublisherSignatureVerifyFailed, SignatureVerifyFailed};
 use crate::{
-    AmountLimit, IostAction, NumberBytes, Read, ReadError, SerializeData, Signature, Write,
-    WriteError,
+    AccountName, AccountPermission, AmountLimit, IostAction, NumberBytes, Read, ReadError,
+    SerializeData, Signature, Write, WriteError,
 };
 use chrono::{DateTime, Duration, TimeZone, Timelike, Utc};
 use keys::algorithm;
+use keys::batch::{verify_batch, BatchItem};
+use keys::error::Error::{BatchVerifyFailed, NonCanonicalSignature, VerifyFailed};
+use keys::signer::Signer;
 use lite_json::{JsonObject, JsonValue, NumberValue, Serialize};
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 use serde::{ser::Serializer, Deserialize, Deserializer, Serialize as SerSerialize};
 use sha3::{Digest, Sha3_256};
 
+#[cfg(feature = "std")]
+use crate::AsyncSigner;
+
 #[derive(Clone, Default, Debug, Read, Write, NumberBytes, SerializeData)]
-#[cfg_attr(feature = "std", derive(Deserialize, SerSerialize))]
+#[cfg_attr(feature = "json", derive(Deserialize, SerSerialize))]
 #[iost_root_path = "crate"]
 pub struct Tx {
     /// Time of transaction. Unixepoch start in nanoseconds
@@ -35,11 +41,11 @@ pub struct Tx {
     /// Token restrictions on transactions. You can specify multiple tokens and a corresponding number limit. If the transaction exceeds these limits, execution fails
     pub amount_limit: Vec<AmountLimit>,
     /// ID of the transaction sender
-    pub publisher: String,
+    pub publisher: AccountName,
     /// Publisher's signature. The signing process is as follows. Publisher can provide multiple signatures with different permissions. You can refer to the documentation of the permission system
     pub publisher_sigs: Vec<Signature>,
-    /// Signer ID other than publisher. It can be empty.
-    pub signers: Vec<String>,
+    /// Signer ID other than publisher, e.g. acct@active. It can be empty.
+    pub signers: Vec<AccountPermission>,
     /// Signature of signers. Each signer can have one or more signatures, so the length is not less than the length of signers
     pub signatures: Vec<Signature>,
 }
@@ -55,14 +61,42 @@ where
     Ok(())
 }
 
+fn expand_signatures(signatures: &[Signature], data: &mut Vec<u8>) -> crate::Result<()> {
+    data.extend_from_slice(&(signatures.len() as u32).to_be_bytes());
+    for signature in signatures {
+        let bytes = signature.to_bytes()?;
+        data.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        data.extend_from_slice(&bytes);
+    }
+    Ok(())
+}
+
 impl Tx {
-    pub fn new(time: i64, expiration: i64, chain_id: u32, actions: Vec<IostAction>) -> Self {
-        let amount_limit = AmountLimit {
-            token: "*".to_string(),
-            value: "unlimited".to_string(),
-        };
+    /// Transaction of `actions`, with tight amount limits covering their transfers, see
+    /// `AmountLimit::from_actions`. Fails with `AmountLimitRequired` for actions spending tokens
+    /// otherwise, e.g. `ram.iost/buy`, whose limits are stated with `with_amount_limit`.
+    pub fn new(
+        time: i64,
+        expiration: i64,
+        chain_id: u32,
+        actions: Vec<IostAction>,
+    ) -> crate::Result<Self> {
+        let amount_limit = AmountLimit::from_actions(&actions, &[])?;
+        Tx::with_amount_limit(time, expiration, chain_id, actions, amount_limit)
+    }
 
-        Tx {
+    /// Transaction of `actions` with the stated `amount_limit`, which has to cover their
+    /// transfers
+    pub fn with_amount_limit(
+        time: i64,
+        expiration: i64,
+        chain_id: u32,
+        actions: Vec<IostAction>,
+        amount_limit: Vec<AmountLimit>,
+    ) -> crate::Result<Self> {
+        AmountLimit::check(&amount_limit, &actions, &[])?;
+
+        Ok(Tx {
             time,
             expiration,
             gas_ratio: 1.0,
@@ -70,36 +104,32 @@ impl Tx {
             delay: 0,
             chain_id,
             actions,
-            amount_limit: vec![amount_limit],
-            publisher: "".to_string(),
+            amount_limit,
+            publisher: AccountName::default(),
             publisher_sigs: vec![],
             signers: vec![],
             signatures: vec![],
-        }
+        })
     }
 
     #[cfg(feature = "std")]
-    pub fn from_action(actions: Vec<IostAction>) -> Self {
-        let amount_limit = AmountLimit {
-            token: "*".to_string(),
-            value: "unlimited".to_string(),
-        };
+    pub fn from_action(actions: Vec<IostAction>) -> crate::Result<Self> {
         let time = Utc::now().timestamp_nanos();
         let expiration = time + Duration::seconds(10000).num_nanoseconds().unwrap();
-        Tx {
-            time,
-            expiration,
-            gas_ratio: 1.0,
-            gas_limit: 1000000.0,
-            delay: 0,
-            chain_id: 1024,
-            actions,
-            amount_limit: vec![amount_limit],
-            publisher: "".to_string(),
-            publisher_sigs: vec![],
-            signers: vec![],
-            signatures: vec![],
+        Tx::new(time, expiration, 1024, actions)
+    }
+
+    /// Checks that the amount limits cover the transfers of the publisher and signers, or all
+    /// the transfers before the publisher is known
+    pub fn check_amount_limit(&self) -> crate::Result<()> {
+        let mut payers = vec![];
+        if !self.publisher.is_empty() {
+            payers.push(self.publisher.as_str());
         }
+        for signer in &self.signers {
+            payers.push(signer.account.as_str());
+        }
+        AmountLimit::check(&self.amount_limit, &self.actions, &payers)
     }
 
     pub fn no_std_serialize_vec(self) -> Vec<u8> {
@@ -163,7 +193,7 @@ impl Tx {
                 JsonValue::Array(
                     self.signers
                         .iter()
-                        .map(|e| JsonValue::String(e.chars().collect::<Vec<_>>()))
+                        .map(|e| JsonValue::String(e.to_string().chars().collect::<Vec<_>>()))
                         .collect(),
                 ),
             ),
@@ -191,7 +221,7 @@ impl Tx {
             ),
             (
                 "publisher".chars().collect::<Vec<_>>(),
-                JsonValue::String(self.publisher.chars().collect::<Vec<_>>()),
+                JsonValue::String(self.publisher.as_str().chars().collect::<Vec<_>>()),
             ),
             (
                 "publisher_sigs".chars().collect::<Vec<_>>(),
@@ -211,22 +241,16 @@ impl Tx {
     }
 
     #[inline]
-    fn number_bytes(&self, with_sign: bool) -> usize {
-        let res = 48
-            + self.signers.num_bytes()
+    fn number_bytes(&self) -> usize {
+        48 + self.signers.num_bytes()
             + self.signers.len() * 4
             + self.actions.num_bytes()
             + self.actions.len() * 4
             + self.amount_limit.num_bytes()
-            + self.amount_limit.len() * 4;
-        if with_sign {
-            res + self.signatures.num_bytes() + self.signatures.len() * 4
-        } else {
-            res
-        }
+            + self.amount_limit.len() * 4
     }
 
-    fn write(&self, bytes: &mut [u8], pos: &mut usize, with_sign: bool) -> Result<(), WriteError> {
+    fn write(&self, bytes: &mut [u8], pos: &mut usize) -> Result<(), WriteError> {
         self.time.clone().write(bytes, pos);
         self.expiration.clone().write(bytes, pos);
         let mut ratio = (self.gas_ratio * 100.0) as i64;
@@ -240,80 +264,189 @@ impl Tx {
         0_i32.write(bytes, pos);
 
         self.signers.len().write(bytes, pos)?;
-        expand::<String>(&self.signers, bytes, pos);
+        expand::<AccountPermission>(&self.signers, bytes, pos);
         self.actions.len().write(bytes, pos);
         expand::<IostAction>(&self.actions, bytes, pos);
         self.amount_limit.len().write(bytes, pos);
         expand::<AmountLimit>(&self.amount_limit, bytes, pos);
-        if with_sign {
-            self.signatures.len().write(bytes, pos);
-            expand::<Signature>(&self.signatures, bytes, pos);
-        }
         Ok(())
     }
 
     pub fn customized_to_serialize_data(&self, with_sign: bool) -> crate::Result<Vec<u8>> {
-        let mut data = vec![0u8; self.number_bytes(with_sign)];
-        self.write(&mut data, &mut 0, with_sign)
-            .map_err(crate::Error::BytesWriteError)
-            .unwrap();
-        Ok(data.to_vec())
+        let mut data = vec![0u8; self.number_bytes()];
+        self.write(&mut data, &mut 0)
+            .map_err(crate::Error::BytesWriteError)?;
+        if with_sign {
+            expand_signatures(&self.signatures, &mut data)?;
+        }
+        Ok(data)
+    }
+
+    /// Hash of the transaction, as returned by `sendTx` and taken by `getTxByHash`. It is the
+    /// SHA3-256 of the full serialization as go-iost's `Tx.ToBytes(Full)`: the signed data, the
+    /// referred transaction, always empty here, then the publisher and publisher signatures
+    pub fn hash(&self) -> crate::Result<String> {
+        let mut data = self.customized_to_serialize_data(true)?;
+        // empty referred tx
+        data.extend_from_slice(&0_u32.to_be_bytes());
+        data.extend_from_slice(&(self.publisher.as_str().len() as u32).to_be_bytes());
+        data.extend_from_slice(self.publisher.as_str().as_bytes());
+        expand_signatures(&self.publisher_sigs, &mut data)?;
+
+        let mut hasher = Sha3_256::new();
+        hasher.input(data);
+        Ok(bs58::encode(hasher.result()).into_string())
+    }
+
+    /// SHA3-256 digest of the transaction, as signed by the signers, or by the publisher when
+    /// `with_sign`
+    pub fn digest(&self, with_sign: bool) -> crate::Result<[u8; 32]> {
+        let mut hasher = Sha3_256::new();
+        hasher.input(self.customized_to_serialize_data(with_sign)?);
+        let mut digest = [0u8; 32];
+        digest.copy_from_slice(&hasher.result());
+        Ok(digest)
     }
 
-    pub fn sign(
+    /// Sets the publisher to `account_name` and checks the amount limits. A signature of another
+    /// publisher is dropped, to be signed again
+    fn set_publisher(&mut self, account_name: String) -> crate::Result<()> {
+        let publisher: AccountName = account_name.parse()?;
+        if publisher != self.publisher {
+            self.publisher_sigs = vec![];
+        }
+        self.publisher = publisher;
+        self.check_amount_limit()
+    }
+
+    /// Signs the transaction as its publisher `account_name`, once its amount limits are checked
+    pub fn sign<S: Signer + ?Sized>(
         &mut self,
         account_name: String,
-        sign_algorithm: &str,
-        sec_key: &[u8],
-    ) -> Result<(), WriteError> {
-        self.publisher = account_name;
-
-        if self.publisher_sigs.len() == 0 {
-            let tx_bytes = self.customized_to_serialize_data(true).unwrap();
-            // create a SHA3-256 object
-            let mut hasher = Sha3_256::new();
-            hasher.input(tx_bytes);
-            let result = hasher.result();
-            self.publisher_sigs =
-                vec![Signature::sign(result.as_ref(), sign_algorithm, sec_key).unwrap()];
+        signer: &S,
+    ) -> crate::Result<()> {
+        self.set_publisher(account_name)?;
+
+        if self.publisher_sigs.is_empty() {
+            self.publisher_sigs = vec![Signature::sign(&self.digest(true)?, signer)?];
         }
         Ok(())
     }
 
-    pub fn verify(&self) -> crate::Result<()> {
-        for signature in &self.signatures {
-            let tx_bytes = self.customized_to_serialize_data(false).unwrap();
-            let mut hasher = Sha3_256::new();
-            hasher.input(tx_bytes);
-            let result = hasher.result();
-            if !signature.verify(result.as_slice()) {
-                return Err(InvalidSignature());
-            }
+    /// Same as `sign`, with a signer answering asynchronously
+    #[cfg(feature = "std")]
+    pub async fn sign_async<S: AsyncSigner + ?Sized>(
+        &mut self,
+        account_name: String,
+        signer: &S,
+    ) -> crate::Result<()> {
+        self.set_publisher(account_name)?;
+
+        if self.publisher_sigs.is_empty() {
+            let digest = self.digest(true)?;
+            let signature = signer
+                .sign_digest(&digest)
+                .await
+                .map_err(crate::Error::Keys)?;
+            self.publisher_sigs = vec![Signature::from_parts(&signer.public_key(), &signature)];
         }
-        for publisher_sig in &self.publisher_sigs {
-            let tx_bytes = self.customized_to_serialize_data(true).unwrap();
-            let mut hasher = Sha3_256::new();
-            hasher.input(tx_bytes);
-            let result = hasher.result();
-            if !publisher_sig.verify(result.as_slice()) {
-                return Err(InvalidPublisherSignature());
-            }
+        Ok(())
+    }
+
+    /// Adds `signer`, e.g. `"acct@active"`, to the signers of the transaction. Signatures collected
+    /// so far no longer match the transaction and are dropped
+    pub fn add_signer(&mut self, signer: &str) -> crate::Result<()> {
+        let signer: AccountPermission = signer.parse()?;
+        if !self.signers.contains(&signer) {
+            self.signers.push(signer);
+            self.signatures = vec![];
+            self.publisher_sigs = vec![];
         }
         Ok(())
     }
+
+    /// Signs the transaction as one of its signers. The publisher signs after all signers, as the
+    /// publisher signature covers theirs
+    pub fn sign_as_signer<S: Signer + ?Sized>(&mut self, signer: &S) -> crate::Result<()> {
+        let signature = Signature::sign(&self.digest(false)?, signer)?;
+        self.signatures.push(signature);
+        self.publisher_sigs = vec![];
+        Ok(())
+    }
+
+    /// Same as `sign_as_signer`, with a signer answering asynchronously
+    #[cfg(feature = "std")]
+    pub async fn sign_as_signer_async<S: AsyncSigner + ?Sized>(
+        &mut self,
+        signer: &S,
+    ) -> crate::Result<()> {
+        let digest = self.digest(false)?;
+        let signature = signer
+            .sign_digest(&digest)
+            .await
+            .map_err(crate::Error::Keys)?;
+        self.signatures
+            .push(Signature::from_parts(&signer.public_key(), &signature));
+        self.publisher_sigs = vec![];
+        Ok(())
+    }
+
+    /// Verifies the signatures of the signers and of the publisher, all in one batch. The first
+    /// wrong signature is reported by its index
+    pub fn verify(&self) -> crate::Result<()> {
+        let digest = self.digest(false)?;
+        let publisher_digest = if self.publisher_sigs.is_empty() {
+            digest
+        } else {
+            self.digest(true)?
+        };
+        let decoded = self
+            .signatures
+            .iter()
+            .chain(self.publisher_sigs.iter())
+            .map(Signature::decode)
+            .collect::<crate::Result<Vec<_>>>()?;
+        let items: Vec<BatchItem> = decoded
+            .iter()
+            .enumerate()
+            .map(|(i, (public_key, sig))| {
+                let message = if i < self.signatures.len() {
+                    &digest
+                } else {
+                    &publisher_digest
+                };
+                (&message[..], *public_key, sig.as_slice())
+            })
+            .collect();
+        match verify_batch(&items) {
+            Err(BatchVerifyFailed(index, e)) => match *e {
+                VerifyFailed | NonCanonicalSignature if index < self.signatures.len() => {
+                    Err(SignatureVerifyFailed(index))
+                }
+                VerifyFailed | NonCanonicalSignature => Err(PublisherSignatureVerifyFailed(
+                    index - self.signatures.len(),
+                )),
+                e => Err(crate::Error::Keys(e)),
+            },
+            result => result.map_err(crate::Error::Keys),
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use keys::keypair::KeyPair;
+    use keys::keystore::{Keystore, ScryptParams};
 
     #[test]
     fn test_bytes_serialization() {
         let tx = Tx::from_action(vec![IostAction {
-            contract: "token.iost".to_string().into_bytes(),
+            contract: "token.iost".parse().unwrap(),
             action_name: "transfer".to_string().into_bytes(),
             data: r#"["iost","admin","lispczz3","100",""]"#.to_string().into_bytes(),
-        }]);
+        }])
+        .unwrap();
 
         let mut data = tx.to_serialize_data().unwrap();
         // assert!(data.is_ok());
@@ -328,22 +461,20 @@ mod test {
     fn test_send_tx() {
         let action = IostAction::transfer("lispczz4", "lispczz5", "10", "").unwrap();
         // let mut tx = Tx::from_action(vec![Action {
-        //     contract: "token.iost".to_string().into_bytes(),
+        //     contract: "token.iost".parse().unwrap(),
         //     action_name: "transfer".to_string().into_bytes(),
         //     data: r#"["iost","lispczz4","lispczz5","8",""]"#.to_string().into_bytes(),
         // }]);
 
-        let mut tx = Tx::from_action(vec![action]);
+        let mut tx = Tx::from_action(vec![action]).unwrap();
         // let sec_key = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".from_base58().unwrap();
-        let sec_key = bs58::decode("xjggJ3TrLXz7qEwrGG3Rc4Fz59imjixhXpViq9W7Ncx")
-            .into_vec()
-            .unwrap();
-        // let sec_key = base64::decode("2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1").unwrap();
-        tx.sign(
-            "lispczz4".to_string(),
+        let key_pair = KeyPair::from_secret_base58(
             algorithm::SECP256K1,
-            sec_key.as_slice(),
-        );
+            "xjggJ3TrLXz7qEwrGG3Rc4Fz59imjixhXpViq9W7Ncx",
+        )
+        .unwrap();
+        // let sec_key = base64::decode("2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1").unwrap();
+        tx.sign("lispczz4".to_string(), &key_pair).unwrap();
         let result = tx.verify();
         assert!(result.is_ok());
 
@@ -370,24 +501,21 @@ mod test {
             delay: 0,
             chain_id: 1024,
             actions: vec![IostAction {
-                contract: "token.iost".to_string().into_bytes(),
+                contract: "token.iost".parse().unwrap(),
                 action_name: "transfer".to_string().into_bytes(),
                 data: r#"["iost","admin","lispczz3","100",""]"#.to_string().into_bytes(),
             }],
-            amount_limit: vec![AmountLimit {
-                token: "*".to_string(),
-                value: "unlimited".to_string(),
-            }],
-            publisher: "".to_string(),
+            amount_limit: vec![AmountLimit::unlimited()],
+            publisher: AccountName::default(),
             publisher_sigs: vec![],
             signers: vec![],
             signatures: vec![],
         };
 
         // let data: Vec<u8> = tx.to_serialize_data().unwrap();
-        let sec_key = bs58::decode("2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1").into_vec().unwrap();
+        let key_pair = KeyPair::from_secret_base58(algorithm::ED25519, "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1").unwrap();
         // let sec_key = base64::decode("2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1").unwrap();
-        tx.sign("admin".to_string(), algorithm::ED25519, sec_key.as_slice());
+        tx.sign("admin".to_string(), &key_pair).unwrap();
         // let s = String::from_utf8(data.clone());
         // dbg!(hex::encode(data.as_slice()));
         let result = tx.verify();
@@ -421,15 +549,12 @@ mod test {
             delay: 0,
             chain_id: 1024,
             actions: vec![ IostAction {
-                contract: "token.iost".to_string().into_bytes(),
+                contract: "token.iost".parse().unwrap(),
                 action_name: "transfer".to_string().into_bytes(),
                 data: r#"["iost", "testaccount", "anothertest", "100", "this is an example transfer"]"#.to_string().into_bytes(),
             }],
-            amount_limit: vec![ AmountLimit {
-                token: "*".to_string(),
-                value: "unlimited".to_string()
-            }],
-            publisher: "".to_string(),
+            amount_limit: vec![ AmountLimit::unlimited()],
+            publisher: AccountName::default(),
             publisher_sigs: vec![],
             signers: vec![],
             signatures: vec![]
@@ -449,26 +574,20 @@ mod test {
             delay: 0,
             chain_id: 1024,
             actions: vec![ IostAction {
-                contract: "token.iost".to_string().into_bytes(),
+                contract: "token.iost".parse().unwrap(),
                 action_name: "transfer".to_string().into_bytes(),
                 data: "[\"iost\", \"testaccount\", \"anothertest\", \"100\", \"this is an example transfer\"]".to_string().into_bytes(),
             }],
-            amount_limit: vec![ AmountLimit {
-                token: "*".to_string(),
-                value: "unlimited".to_string()
-            }],
-            publisher: "".to_string(),
+            amount_limit: vec![ AmountLimit::unlimited()],
+            publisher: AccountName::default(),
             publisher_sigs: vec![],
             signers: vec![],
             signatures: vec![]
         };
 
         let sec_key = base64::decode("gkpobuI3gbFGstgfdymLBQAGR67ulguDzNmLXEJSWaGUNL5J0z5qJUdsPJdqm+uyDIrEWD2Ym4dY9lv8g0FFZg==").unwrap();
-        tx.sign(
-            "testaccount".to_string(),
-            algorithm::ED25519,
-            sec_key.as_slice(),
-        );
+        let key_pair = KeyPair::from_secret_key(algorithm::ED25519, &sec_key).unwrap();
+        tx.sign("testaccount".to_string(), &key_pair).unwrap();
         assert!(tx.verify().is_ok());
 
         let tx_str = r#"
@@ -516,4 +635,353 @@ mod test {
             );
         }
     }
+
+    // The publisher signature is the one of the `sendTx` example of the IOST API docs. The
+    // hashes are computed apart from this crate, following go-iost's `Tx.ToBytes(Full)`
+    #[test]
+    fn should_tx_hash_be_ok() {
+        let mut tx = Tx {
+            time: 1544709662543340000,
+            expiration: 1544709692318715000,
+            gas_ratio: 1.0,
+            gas_limit: 500000.0,
+            delay: 0,
+            chain_id: 1024,
+            actions: vec![ IostAction {
+                contract: "token.iost".parse().unwrap(),
+                action_name: "transfer".to_string().into_bytes(),
+                data: "[\"iost\", \"testaccount\", \"anothertest\", \"100\", \"this is an example transfer\"]".to_string().into_bytes(),
+            }],
+            amount_limit: vec![ AmountLimit::unlimited()],
+            publisher: AccountName::default(),
+            publisher_sigs: vec![],
+            signers: vec![],
+            signatures: vec![]
+        };
+        assert_eq!(
+            tx.hash().unwrap(),
+            "5aMhdpRjpbzZZayndsYkL2LXURrdhYbiLpR5p1hMoimZ"
+        );
+
+        let sec_key = base64::decode("gkpobuI3gbFGstgfdymLBQAGR67ulguDzNmLXEJSWaGUNL5J0z5qJUdsPJdqm+uyDIrEWD2Ym4dY9lv8g0FFZg==").unwrap();
+        let key_pair = KeyPair::from_secret_key(algorithm::ED25519, &sec_key).unwrap();
+        tx.sign("testaccount".to_string(), &key_pair).unwrap();
+        assert_eq!(
+            hex::encode(tx.publisher_sigs[0].to_bytes().unwrap()),
+            "0200000040fcad473343846df278f83dc199a9692e66f4dd64bb05e0b3e2754704d6c3af1dff037d5a376449371c842a1bfe552a1672d7debc33519cbd6469d455c52b7c08000000209434be49d33e6a25476c3c976a9bebb20c8ac4583d989b8758f65bfc83414566"
+        );
+        assert_eq!(
+            tx.hash().unwrap(),
+            "CihRgNwEPjWnaXgogcFhK31r7EkWZyU9H6ZxzyWF37yK"
+        );
+
+        // co-signed by anothertest, whose signature the publisher signature covers
+        let co_signer = KeyPair::from_secret_base58(algorithm::ED25519, "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1").unwrap();
+        let mut co_signed = tx.clone();
+        co_signed.add_signer("anothertest@active").unwrap();
+        co_signed.sign_as_signer(&co_signer).unwrap();
+        co_signed
+            .sign("testaccount".to_string(), &key_pair)
+            .unwrap();
+        assert_eq!(
+            co_signed.hash().unwrap(),
+            "5J2jKVQVjxjaB1UHri7sCjAbHGXBkddSaq8WYidsheLr"
+        );
+
+        tx.publisher_sigs[0].algorithm = "RSA".to_string();
+        assert!(tx.hash().is_err());
+    }
+
+    #[test]
+    fn should_multi_signer_tx_verify() {
+        let mut tx = Tx::new(
+            1544709662543340000,
+            1544709692318715000,
+            1024,
+            vec![IostAction::transfer("treasury", "lispczz3", "100", "").unwrap()],
+        )
+        .unwrap();
+        let publisher_key = base64::decode("gkpobuI3gbFGstgfdymLBQAGR67ulguDzNmLXEJSWaGUNL5J0z5qJUdsPJdqm+uyDIrEWD2Ym4dY9lv8g0FFZg==").unwrap();
+        let publisher_key = KeyPair::from_secret_key(algorithm::ED25519, &publisher_key).unwrap();
+        let ed25519_key = KeyPair::from_secret_base58(algorithm::ED25519, "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1").unwrap();
+        let secp256k1_key = KeyPair::from_secret_base58(
+            algorithm::SECP256K1,
+            "xjggJ3TrLXz7qEwrGG3Rc4Fz59imjixhXpViq9W7Ncx",
+        )
+        .unwrap();
+
+        tx.add_signer("treasury@active").unwrap();
+        tx.add_signer("admin@active").unwrap();
+        tx.add_signer("admin@active").unwrap();
+        assert_eq!(tx.signers, vec!["treasury@active", "admin@active"]);
+
+        tx.sign_as_signer(&ed25519_key).unwrap();
+        tx.sign_as_signer(&secp256k1_key).unwrap();
+        tx.sign("testaccount".to_string(), &publisher_key).unwrap();
+        assert_eq!(tx.signatures.len(), 2);
+        assert_eq!(tx.publisher_sigs.len(), 1);
+        assert!(tx.verify().is_ok());
+
+        // the publisher signature covers the signatures of signers
+        let mut tampered = tx.clone();
+        tampered.signatures.pop();
+        assert!(matches!(
+            tampered.verify(),
+            Err(crate::Error::PublisherSignatureVerifyFailed(0))
+        ));
+
+        let mut tampered = tx.clone();
+        tampered.signatures.swap(0, 1);
+        tampered.signatures[0].signature = tx.signatures[0].signature.clone();
+        assert!(matches!(
+            tampered.verify(),
+            Err(crate::Error::SignatureVerifyFailed(0))
+        ));
+
+        // signing after the publisher drops the stale publisher signature
+        tx.sign_as_signer(&publisher_key).unwrap();
+        assert!(tx.publisher_sigs.is_empty());
+        tx.sign("testaccount".to_string(), &publisher_key).unwrap();
+        assert!(tx.verify().is_ok());
+
+        // another publisher signs again
+        let publisher_sig = tx.publisher_sigs[0].clone();
+        tx.sign("admin".to_string(), &ed25519_key).unwrap();
+        assert_eq!(tx.publisher, "admin");
+        assert_eq!(tx.publisher_sigs.len(), 1);
+        assert_ne!(tx.publisher_sigs[0].public_key, publisher_sig.public_key);
+        assert!(tx.verify().is_ok());
+
+        tx.add_signer("lispczz3@active").unwrap();
+        assert!(tx.signatures.is_empty());
+        assert!(tx.publisher_sigs.is_empty());
+
+        // invalid names are rejected before signing
+        assert!(matches!(
+            tx.add_signer("lispczz3"),
+            Err(crate::Error::InvalidAccountName(_))
+        ));
+        assert!(matches!(
+            tx.sign("eosio.token".to_string(), &publisher_key),
+            Err(crate::Error::InvalidAccountName(_))
+        ));
+        assert!(tx.publisher_sigs.is_empty());
+    }
+
+    #[test]
+    fn should_tx_amount_limit_cover_transfers() {
+        let key_pair: KeyPair = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".parse().unwrap();
+        let mut tx = Tx::new(
+            1544709662543340000,
+            1544709692318715000,
+            1024,
+            vec![
+                IostAction::transfer("admin", "lispczz3", "100", "").unwrap(),
+                IostAction::transfer("admin", "lispczz4", "0.5", "").unwrap(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(tx.amount_limit.len(), 1);
+        assert_eq!(tx.amount_limit[0].token, "iost");
+        assert_eq!(tx.amount_limit[0].value, "100.5");
+
+        tx.amount_limit = vec![AmountLimit::exact("iost", "100".parse().unwrap()).unwrap()];
+        assert!(matches!(
+            tx.sign("admin".to_string(), &key_pair),
+            Err(crate::Error::AmountLimitExceeded(_))
+        ));
+        assert!(tx.publisher_sigs.is_empty());
+
+        // transfers of other accounts are not limited
+        tx.sign("treasury".to_string(), &key_pair).unwrap();
+        assert!(tx.verify().is_ok());
+    }
+
+    #[test]
+    fn should_tx_require_amount_limit_of_ram_buy() {
+        use crate::ContractAction;
+
+        let buy = crate::ram::Buy {
+            payer: "admin".parse().unwrap(),
+            account: "lispczz3".parse().unwrap(),
+            bytes: 1024,
+        }
+        .to_action()
+        .unwrap();
+        assert!(matches!(
+            Tx::new(1544709662543340000, 1544709692318715000, 1024, vec![buy.clone()]),
+            Err(crate::Error::AmountLimitRequired(ref call)) if call == "ram.iost/buy"
+        ));
+
+        let limit = AmountLimit::exact("iost", "0.2".parse().unwrap()).unwrap();
+        let tx = Tx::with_amount_limit(
+            1544709662543340000,
+            1544709692318715000,
+            1024,
+            vec![buy],
+            vec![limit.clone()],
+        )
+        .unwrap();
+        assert_eq!(tx.amount_limit, vec![limit]);
+
+        // stated limits still cover the transfers
+        let transfer = IostAction::transfer("admin", "lispczz3", "1", "").unwrap();
+        assert!(matches!(
+            Tx::with_amount_limit(
+                1544709662543340000,
+                1544709692318715000,
+                1024,
+                vec![transfer],
+                vec![AmountLimit::exact("iost", "0.2".parse().unwrap()).unwrap()],
+            ),
+            Err(crate::Error::AmountLimitExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn should_tx_sign_with_keystore_key() {
+        let key_pair: KeyPair = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".parse().unwrap();
+        let mut keystore = Keystore::new().with_kdf(ScryptParams {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        });
+        keystore
+            .insert("admin", "active", &key_pair, "password")
+            .unwrap();
+        let keystore = Keystore::from_json(&keystore.to_json().unwrap()).unwrap();
+
+        let mut tx = Tx::new(
+            1544709662543340000,
+            1544709692318715000,
+            1024,
+            vec![IostAction::transfer("admin", "lispczz3", "100", "").unwrap()],
+        )
+        .unwrap();
+        let signer = keystore.unlock("admin", "active", "password").unwrap();
+        tx.sign("admin".to_string(), &signer).unwrap();
+        assert!(tx.verify().is_ok());
+        assert_eq!(
+            tx.publisher_sigs[0].public_key,
+            key_pair.public().to_base64()
+        );
+    }
+
+    #[test]
+    fn should_tx_sign_through_signers() {
+        let key_pair: KeyPair = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".parse().unwrap();
+        let mut keystore = Keystore::new().with_kdf(ScryptParams {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        });
+        keystore
+            .insert("treasury", "active", &key_pair, "password")
+            .unwrap();
+        let keystore_signer = keystore.signer("treasury", "active", "password").unwrap();
+
+        let path = std::env::temp_dir().join(format!("iost-tx-signer-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = std::os::unix::net::UnixListener::bind(&path).unwrap();
+        let publisher_key = KeyPair::from_secret_base58(
+            algorithm::SECP256K1,
+            "xjggJ3TrLXz7qEwrGG3Rc4Fz59imjixhXpViq9W7Ncx",
+        )
+        .unwrap();
+        let served_key = publisher_key.clone();
+        std::thread::spawn(move || keys::remote::serve(&served_key, &listener));
+        let remote_signer = keys::remote::RemoteSigner::connect(&path).unwrap();
+
+        let mut tx = Tx::new(
+            1544709662543340000,
+            1544709692318715000,
+            1024,
+            vec![IostAction::transfer("treasury", "lispczz3", "100", "").unwrap()],
+        )
+        .unwrap();
+        tx.add_signer("treasury@active").unwrap();
+        let signers: Vec<&dyn Signer> = vec![&keystore_signer];
+        for signer in signers {
+            tx.sign_as_signer(signer).unwrap();
+        }
+        tx.sign("admin".to_string(), &remote_signer).unwrap();
+        assert!(tx.verify().is_ok());
+        assert_eq!(tx.signatures[0].public_key, key_pair.public().to_base64());
+        assert_eq!(tx.publisher_sigs[0].algorithm, algorithm::SECP256K1);
+        assert_eq!(
+            tx.publisher_sigs[0].public_key,
+            publisher_key.public().to_base64()
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_tx_sign_async() {
+        let key_pair: KeyPair = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".parse().unwrap();
+        let mut tx = Tx::new(
+            1544709662543340000,
+            1544709692318715000,
+            1024,
+            vec![IostAction::transfer("admin", "lispczz3", "100", "").unwrap()],
+        )
+        .unwrap();
+        tx.add_signer("admin@active").unwrap();
+        tx.sign_as_signer_async(&key_pair).await.unwrap();
+        tx.sign_async("admin".to_string(), &key_pair).await.unwrap();
+        assert!(tx.verify().is_ok());
+
+        let mut sync_tx = tx.clone();
+        sync_tx.signatures.clear();
+        sync_tx.publisher_sigs.clear();
+        sync_tx.sign_as_signer(&key_pair).unwrap();
+        sync_tx.sign("admin".to_string(), &key_pair).unwrap();
+        assert_eq!(sync_tx.signatures[0].signature, tx.signatures[0].signature);
+        assert_eq!(
+            sync_tx.publisher_sigs[0].signature,
+            tx.publisher_sigs[0].signature
+        );
+    }
+
+    #[test]
+    fn should_tx_verify_report_malformed_signatures() {
+        let sec_key = bs58::decode("2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1").into_vec().unwrap();
+        let mut tx = Tx::new(
+            1544709662543340000,
+            1544709692318715000,
+            1024,
+            vec![IostAction::transfer("admin", "lispczz3", "100", "").unwrap()],
+        )
+        .unwrap();
+        assert!(matches!(
+            KeyPair::from_secret_key("RSA", &sec_key),
+            Err(keys::error::Error::UnknownAlgorithm(_))
+        ));
+        assert!(matches!(
+            KeyPair::from_secret_key(algorithm::ED25519, &sec_key[..10]),
+            Err(keys::error::Error::InvalidSecretKeyLength(10))
+        ));
+
+        let key_pair = KeyPair::from_secret_key(algorithm::ED25519, &sec_key).unwrap();
+        tx.sign("admin".to_string(), &key_pair).unwrap();
+        let mut malformed = tx.clone();
+        malformed.publisher_sigs[0].signature = "not base64!".to_string();
+        assert!(matches!(
+            malformed.verify(),
+            Err(crate::Error::InvalidSignature())
+        ));
+
+        let mut malformed = tx.clone();
+        malformed.publisher_sigs[0].public_key = base64::encode(&[1u8; 3]);
+        assert!(matches!(
+            malformed.verify(),
+            Err(crate::Error::Keys(keys::error::Error::ErrorEd25519))
+        ));
+
+        let mut malformed = tx;
+        malformed.publisher_sigs[0].algorithm = "RSA".to_string();
+        assert!(matches!(
+            malformed.verify(),
+            Err(crate::Error::Keys(keys::error::Error::UnknownAlgorithm(_)))
+        ));
+    }
 }
@@ -1,9 +1,9 @@
 use crate::bytes::{NumberBytes, Read, ReadError, Write, WriteError};
-#[cfg(feature = "std")]
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct UnsignedInt(u32);
 
 impl From<usize> for UnsignedInt {
@@ -0,0 +1,499 @@
+//! Typed actions of the `vote_producer.iost` contract, which elects the block producers, and
+//! decoders of its receipts and storage
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use keys::public::PublicKey;
+use lite_json::{parse_json, JsonValue};
+
+use crate::contract_action::{
+    arg_bool, arg_key, arg_parse, arg_string, field, json_bool, json_string, json_strings,
+    parse_args, string_arg,
+};
+use crate::Error::{JsonParserError, UnexpectedAction};
+use crate::{AccountName, Amount, ContractAction, ContractStorage, IostAction, Receipt};
+
+/// Id of the producer election contract
+pub const VOTE_PRODUCER_CONTRACT: &str = "vote_producer.iost";
+
+/// Function name of the receipt carrying the producer lists, emitted at each election
+pub const STAT_RECEIPT: &str = "vote_producer.iost/stat";
+
+/// Storage map of the registered producers, by account name
+pub const PRODUCER_TABLE: &str = "producerTable";
+/// Storage key of the public keys of the producers of the current epoch
+pub const CURRENT_PRODUCER_LIST: &str = "currentProducerList";
+/// Storage key of the public keys of the producers elected for the next epoch
+pub const PENDING_PRODUCER_LIST: &str = "pendingProducerList";
+
+/// Id of the contract storing the votes of the voters
+pub const VOTE_CONTRACT: &str = "vote.iost";
+/// Storage map of the votes of each voter in the producer election, by voter name
+pub const VOTER_TABLE: &str = "u_1";
+
+/// Vote of `amount` IOST by `voter` for `producer`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Vote {
+    pub voter: AccountName,
+    pub producer: AccountName,
+    pub amount: Amount,
+}
+
+impl ContractAction for Vote {
+    const CONTRACT: &'static str = VOTE_PRODUCER_CONTRACT;
+    const ACTION_NAME: &'static str = "vote";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.voter),
+            string_arg(&self.producer),
+            string_arg(&self.amount),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(Vote {
+            voter: arg_parse(args, 0)?,
+            producer: arg_parse(args, 1)?,
+            amount: arg_parse(args, 2)?,
+        })
+    }
+}
+
+/// Withdrawal of `amount` IOST voted by `voter` for `producer`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Unvote {
+    pub voter: AccountName,
+    pub producer: AccountName,
+    pub amount: Amount,
+}
+
+impl ContractAction for Unvote {
+    const CONTRACT: &'static str = VOTE_PRODUCER_CONTRACT;
+    const ACTION_NAME: &'static str = "unvote";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.voter),
+            string_arg(&self.producer),
+            string_arg(&self.amount),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(Unvote {
+            voter: arg_parse(args, 0)?,
+            producer: arg_parse(args, 1)?,
+            amount: arg_parse(args, 2)?,
+        })
+    }
+}
+
+/// Candidacy of `account`, producing blocks with `pubkey` on the node `net_id` if
+/// `is_producer`, or as a partner node otherwise
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApplyRegister {
+    pub account: AccountName,
+    pub pubkey: PublicKey,
+    pub location: String,
+    pub url: String,
+    pub net_id: String,
+    pub is_producer: bool,
+}
+
+impl ContractAction for ApplyRegister {
+    const CONTRACT: &'static str = VOTE_PRODUCER_CONTRACT;
+    const ACTION_NAME: &'static str = "applyRegister";
+
+    fn to_args(&self) -> Vec<JsonValue> {
+        vec![
+            string_arg(&self.account),
+            string_arg(&self.pubkey),
+            string_arg(&self.location),
+            string_arg(&self.url),
+            string_arg(&self.net_id),
+            JsonValue::Boolean(self.is_producer),
+        ]
+    }
+
+    fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+        Ok(ApplyRegister {
+            account: arg_parse(args, 0)?,
+            pubkey: arg_key(args, 1)?,
+            location: arg_string(args, 2)?,
+            url: arg_string(args, 3)?,
+            net_id: arg_string(args, 4)?,
+            is_producer: arg_bool(args, 5)?,
+        })
+    }
+}
+
+/// Defines an action of the contract taking the account name as its only argument
+macro_rules! account_action {
+    ($(#[$doc:meta])* $name:ident, $action_name:literal, $account:ident) => {
+        $(#[$doc])*
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct $name {
+            pub $account: AccountName,
+        }
+
+        impl ContractAction for $name {
+            const CONTRACT: &'static str = VOTE_PRODUCER_CONTRACT;
+            const ACTION_NAME: &'static str = $action_name;
+
+            fn to_args(&self) -> Vec<JsonValue> {
+                vec![string_arg(&self.$account)]
+            }
+
+            fn from_args(args: &[JsonValue]) -> crate::Result<Self> {
+                Ok($name {
+                    $account: arg_parse(args, 0)?,
+                })
+            }
+        }
+    };
+}
+
+account_action!(
+    /// Withdrawal of the candidacy of `account`
+    ApplyUnregister,
+    "applyUnregister",
+    account
+);
+account_action!(
+    /// Producer `account` going online, to be scheduled again
+    LogInProducer,
+    "logInProducer",
+    account
+);
+account_action!(
+    /// Producer `account` going offline, to be skipped by the schedule
+    LogOutProducer,
+    "logOutProducer",
+    account
+);
+account_action!(
+    /// Withdrawal of the bonus earned by `voter`
+    VoterWithdraw,
+    "voterWithdraw",
+    voter
+);
+
+/// Any decoded action of the producer election contract
+#[derive(Clone, Debug, PartialEq)]
+pub enum VoteProducerAction {
+    Vote(Vote),
+    Unvote(Unvote),
+    ApplyRegister(ApplyRegister),
+    ApplyUnregister(ApplyUnregister),
+    LogInProducer(LogInProducer),
+    LogOutProducer(LogOutProducer),
+    VoterWithdraw(VoterWithdraw),
+}
+
+impl VoteProducerAction {
+    pub fn from_action(action: &IostAction) -> crate::Result<Self> {
+        let action_name = String::from_utf8_lossy(&action.action_name);
+        let unexpected = || UnexpectedAction(format!("{}/{}", action.contract, action_name));
+        if action.contract != VOTE_PRODUCER_CONTRACT {
+            return Err(unexpected());
+        }
+        let args = parse_args(&action.data)?;
+        Ok(match action_name.as_ref() {
+            Vote::ACTION_NAME => VoteProducerAction::Vote(Vote::from_args(&args)?),
+            Unvote::ACTION_NAME => VoteProducerAction::Unvote(Unvote::from_args(&args)?),
+            ApplyRegister::ACTION_NAME => {
+                VoteProducerAction::ApplyRegister(ApplyRegister::from_args(&args)?)
+            }
+            ApplyUnregister::ACTION_NAME => {
+                VoteProducerAction::ApplyUnregister(ApplyUnregister::from_args(&args)?)
+            }
+            LogInProducer::ACTION_NAME => {
+                VoteProducerAction::LogInProducer(LogInProducer::from_args(&args)?)
+            }
+            LogOutProducer::ACTION_NAME => {
+                VoteProducerAction::LogOutProducer(LogOutProducer::from_args(&args)?)
+            }
+            VoterWithdraw::ACTION_NAME => {
+                VoteProducerAction::VoterWithdraw(VoterWithdraw::from_args(&args)?)
+            }
+            _ => return Err(unexpected()),
+        })
+    }
+}
+
+/// Parses storage data, `None` when the key or field is not set
+fn parse_storage(storage: &ContractStorage) -> crate::Result<Option<JsonValue>> {
+    match parse_json(&storage.data) {
+        Ok(JsonValue::Null) => Ok(None),
+        Ok(value) => Ok(Some(value)),
+        Err(_) if storage.data.is_empty() => Ok(None),
+        Err(_) => Err(JsonParserError()),
+    }
+}
+
+/// Producer lists of a `vote_producer.iost/stat` receipt, as public keys
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProducerStat {
+    /// producers elected for the next epoch
+    pub pending_list: Vec<String>,
+    /// producers of the current epoch
+    pub current_list: Vec<String>,
+}
+
+impl ProducerStat {
+    pub fn from_receipt(receipt: &Receipt) -> crate::Result<Self> {
+        if receipt.func_name != STAT_RECEIPT {
+            return Err(UnexpectedAction(receipt.func_name.clone()));
+        }
+        ProducerStat::from_content(&receipt.content)
+    }
+
+    /// Parses the content of a `vote_producer.iost/stat` receipt, of a block as well as of a
+    /// transaction
+    pub fn from_content(content: &str) -> crate::Result<Self> {
+        let content = parse_json(content).map_err(|_| JsonParserError())?;
+        Ok(ProducerStat {
+            pending_list: json_strings(field(&content, "pendingList"))?,
+            current_list: json_strings(field(&content, "currentList"))?,
+        })
+    }
+
+    /// Stat of the first `vote_producer.iost/stat` receipt in `receipts`, if any
+    pub fn find(receipts: &[Receipt]) -> Option<crate::Result<Self>> {
+        receipts
+            .iter()
+            .find(|receipt| receipt.func_name == STAT_RECEIPT)
+            .map(ProducerStat::from_receipt)
+    }
+}
+
+/// Public keys stored at `currentProducerList` or `pendingProducerList`
+pub fn producer_list(storage: &ContractStorage) -> crate::Result<Vec<String>> {
+    match parse_storage(storage)? {
+        Some(list) => json_strings(Some(&list)),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Stage of the registration of a producer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProducerStatus {
+    Applying,
+    Approved,
+    Unapplying,
+    UnapplyApproved,
+}
+
+impl ProducerStatus {
+    pub fn from_code(code: i64) -> crate::Result<Self> {
+        match code {
+            0 => Ok(ProducerStatus::Applying),
+            1 => Ok(ProducerStatus::Approved),
+            2 => Ok(ProducerStatus::Unapplying),
+            3 => Ok(ProducerStatus::UnapplyApproved),
+            _ => Err(JsonParserError()),
+        }
+    }
+
+    pub fn code(self) -> i64 {
+        self as i64
+    }
+}
+
+/// Entry of a candidate in the `producerTable` map
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProducerInfo {
+    pub pubkey: PublicKey,
+    pub location: String,
+    pub url: String,
+    pub net_id: String,
+    pub is_producer: bool,
+    pub status: ProducerStatus,
+    /// whether the producer is logged in
+    pub online: bool,
+}
+
+impl ProducerInfo {
+    pub fn from_json(value: &JsonValue) -> crate::Result<Self> {
+        let status = match field(value, "status") {
+            Some(JsonValue::Number(number)) if number.fraction_length == 0 => number.integer,
+            _ => return Err(JsonParserError()),
+        };
+        Ok(ProducerInfo {
+            pubkey: json_string(field(value, "pubkey"))?
+                .parse()
+                .map_err(crate::Error::Keys)?,
+            location: json_string(field(value, "loc"))?,
+            url: json_string(field(value, "url"))?,
+            net_id: json_string(field(value, "netId"))?,
+            is_producer: json_bool(field(value, "isProducer"))?,
+            status: ProducerStatus::from_code(status)?,
+            online: json_bool(field(value, "online"))?,
+        })
+    }
+
+    /// Decodes `producerTable[account]`, `None` if `account` never applied
+    pub fn from_storage(storage: &ContractStorage) -> crate::Result<Option<Self>> {
+        parse_storage(storage)?
+            .map(|value| ProducerInfo::from_json(&value))
+            .transpose()
+    }
+}
+
+/// Votes of a voter, from the `u_1` map of `vote.iost`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VoterVotes {
+    /// IOST voted for each producer
+    pub votes: Vec<(String, Amount)>,
+}
+
+impl VoterVotes {
+    /// Decodes `{"producer": ["amount", ...]}`, keeping the amount first in each entry
+    pub fn from_json(value: &JsonValue) -> crate::Result<Self> {
+        let fields = match value {
+            JsonValue::Object(fields) => fields,
+            _ => return Err(JsonParserError()),
+        };
+        let votes = fields
+            .iter()
+            .map(|(producer, entry)| {
+                let amount = match entry {
+                    JsonValue::Array(items) => json_string(items.first())?,
+                    _ => return Err(JsonParserError()),
+                };
+                Ok((producer.iter().collect(), amount.parse()?))
+            })
+            .collect::<crate::Result<_>>()?;
+        Ok(VoterVotes { votes })
+    }
+
+    /// Decodes `u_1[voter]`, empty if `voter` never voted
+    pub fn from_storage(storage: &ContractStorage) -> crate::Result<Self> {
+        match parse_storage(storage)? {
+            Some(value) => VoterVotes::from_json(&value),
+            None => Ok(VoterVotes::default()),
+        }
+    }
+
+    /// IOST voted by the voter for `producer`
+    pub fn votes_for(&self, producer: &str) -> Option<Amount> {
+        self.votes
+            .iter()
+            .find(|(name, _)| name == producer)
+            .map(|(_, amount)| *amount)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::string::ToString;
+
+    const PUBKEY: &str = "Gcv8c2tH8qZrUYnKdEEdTtASsxivic2834MQW6mgxqto";
+
+    fn storage(data: &str) -> ContractStorage {
+        ContractStorage {
+            data: data.to_string(),
+            block_hash: String::new(),
+            block_number: String::new(),
+        }
+    }
+
+    #[test]
+    fn vote_producer_actions_should_round_trip() {
+        let vote = Vote {
+            voter: "admin".parse().unwrap(),
+            producer: "producer00".parse().unwrap(),
+            amount: "2100000".parse().unwrap(),
+        };
+        let action = vote.to_action().unwrap();
+        assert_eq!(action.contract, VOTE_PRODUCER_CONTRACT);
+        assert_eq!(action.data, br#"["admin","producer00","2100000"]"#.to_vec());
+        assert_eq!(
+            VoteProducerAction::from_action(&action).unwrap(),
+            VoteProducerAction::Vote(vote)
+        );
+
+        let apply = ApplyRegister {
+            account: "producer00".parse().unwrap(),
+            pubkey: PUBKEY.parse().unwrap(),
+            location: "loc".to_string(),
+            url: "https://iost.io".to_string(),
+            net_id: "12D3KooW".to_string(),
+            is_producer: true,
+        };
+        let action = apply.to_action().unwrap();
+        assert_eq!(
+            action.data,
+            format!(
+                r#"["producer00","{}","loc","https://iost.io","12D3KooW",true]"#,
+                PUBKEY
+            )
+            .into_bytes()
+        );
+        assert_eq!(ApplyRegister::from_action(&action).unwrap(), apply);
+
+        let log_out = LogOutProducer {
+            account: "producer00".parse().unwrap(),
+        };
+        let action = log_out.to_action().unwrap();
+        assert_eq!(action.action_name, b"logOutProducer");
+        assert_eq!(action.data, br#"["producer00"]"#.to_vec());
+        assert_eq!(
+            VoteProducerAction::from_action(&action).unwrap(),
+            VoteProducerAction::LogOutProducer(log_out)
+        );
+        assert!(LogInProducer::from_action(&action).is_err());
+    }
+
+    #[test]
+    fn vote_producer_receipts_and_storage_should_decode() {
+        let receipts = vec![
+            Receipt {
+                func_name: "token.iost/transfer".to_string(),
+                content: r#"["iost","a","b","1",""]"#.to_string(),
+            },
+            Receipt {
+                func_name: STAT_RECEIPT.to_string(),
+                content: r#"{"pendingList":["k1","k2"],"currentList":["k0"]}"#.to_string(),
+            },
+        ];
+        let stat = ProducerStat::find(&receipts).unwrap().unwrap();
+        assert_eq!(stat.pending_list, vec!["k1", "k2"]);
+        assert_eq!(stat.current_list, vec!["k0"]);
+        assert!(ProducerStat::from_receipt(&receipts[0]).is_err());
+        assert!(ProducerStat::find(&receipts[..1]).is_none());
+
+        let info = ProducerInfo::from_storage(&storage(&format!(
+            r#"{{"pubkey":"{}","loc":"","url":"","netId":"","isProducer":true,"status":1,"online":false}}"#,
+            PUBKEY
+        )))
+        .unwrap()
+        .unwrap();
+        assert_eq!(info.pubkey.to_string(), PUBKEY);
+        assert_eq!(info.status, ProducerStatus::Approved);
+        assert!(info.is_producer && !info.online);
+        assert_eq!(ProducerInfo::from_storage(&storage("null")).unwrap(), None);
+
+        assert_eq!(
+            producer_list(&storage(r#"["k0","k1"]"#)).unwrap(),
+            vec!["k0", "k1"]
+        );
+
+        let votes =
+            VoterVotes::from_storage(&storage(r#"{"producer00":["100.5",0,"0"]}"#)).unwrap();
+        assert_eq!(
+            votes.votes_for("producer00"),
+            Some("100.5".parse().unwrap())
+        );
+        assert_eq!(votes.votes_for("producer01"), None);
+        assert!(VoterVotes::from_storage(&storage("null"))
+            .unwrap()
+            .votes
+            .is_empty());
+    }
+}
@@ -0,0 +1,58 @@
+//! Compares checking ed25519 signatures one by one with `verify_batch`.
+//!
+//! `cargo bench -p iost-keys --features batch`; without the feature `verify_batch` checks the
+//! items one by one as well.
+
+use std::time::{Duration, Instant};
+
+use iost_keys::algorithm;
+use iost_keys::batch::{verify_batch, BatchItem};
+use iost_keys::keypair::KeyPair;
+
+const ROUNDS: u32 = 10;
+
+fn time<F: FnMut()>(mut f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    start.elapsed() / ROUNDS
+}
+
+fn main() {
+    let key_pairs: Vec<KeyPair> = (0..32)
+        .map(|_| KeyPair::new(algorithm::ED25519).unwrap())
+        .collect();
+
+    // a block is signed once, a transaction usually once or twice, a witness list is 12+ blocks
+    for &count in [1usize, 2, 16, 256, 1024].iter() {
+        let signed: Vec<([u8; 32], usize, Vec<u8>)> = (0..count)
+            .map(|i| {
+                let message = [(i % 256) as u8; 32];
+                let key = i % key_pairs.len();
+                (message, key, key_pairs[key].sign(&message).unwrap())
+            })
+            .collect();
+        let items: Vec<BatchItem> = signed
+            .iter()
+            .map(|(message, key, signature)| {
+                (&message[..], *key_pairs[*key].public(), &signature[..])
+            })
+            .collect();
+
+        let one_by_one = time(|| {
+            for (message, public_key, signature) in items.iter() {
+                public_key.verify(message, signature).unwrap();
+            }
+        });
+        let batch = time(|| verify_batch(&items).unwrap());
+        println!(
+            "{:>5} signatures: one by one {:>10.3?} ({:>8.3?}/sig), batch {:>10.3?} ({:>8.3?}/sig)",
+            count,
+            one_by_one,
+            one_by_one / count as u32,
+            batch,
+            batch / count as u32,
+        );
+    }
+}
@@ -1,10 +1,15 @@
 use alloc::boxed::Box;
+use alloc::string::ToString;
 use alloc::vec;
 use alloc::vec::Vec;
 use core::convert::{TryFrom, TryInto};
 
-use crate::error::Error::{ErrorEd25519, ErrorSecp256k1};
-use crate::Result;
+use crate::error::Error::{
+    ErrorEd25519, InvalidSecretKey, InvalidSecretKeyLength, NonCanonicalSignature,
+    PublicKeyMismatch, UnknownAlgorithm, VerifyFailed,
+};
+use crate::public::PublicKey;
+use crate::{signature, Result};
 use ed25519_dalek::{Signature, Signer, Verifier};
 #[cfg(feature = "std")]
 use rand::rngs::OsRng;
@@ -18,8 +23,10 @@ const ROOT_KEY: &'static str =
     "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1";
 
 pub trait Algorithm {
-    fn sign(&self, message: &[u8], sec_key: &[u8]) -> Vec<u8>;
-    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> bool;
+    /// Signs `message` after checking `sec_key`
+    fn sign(&self, message: &[u8], sec_key: &[u8]) -> crate::Result<Vec<u8>>;
+    /// Verifies `signature`, failing with `VerifyFailed` if it does not match
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> crate::Result<()>;
     #[cfg(feature = "std")]
     fn gen_sec_key(&self) -> Vec<u8>;
     fn get_pub_key(&self, sec_key: &[u8]) -> crate::Result<Vec<u8>>;
@@ -29,62 +36,92 @@ pub trait Algorithm {
 pub struct AlgorithmSecp256k1;
 pub struct AlgorithmEd25519;
 
-pub fn new(algorithm_name: &str) -> Box<dyn Algorithm> {
-    match algorithm_name {
-        ED25519 => Box::new(AlgorithmEd25519),
-        SECP256K1 => Box::new(AlgorithmSecp256k1),
-        _ => Box::new(AlgorithmSecp256k1),
+/// Algorithm named `algorithm_name`, `ED25519` or `SECP256K1` in any case
+pub fn new(algorithm_name: &str) -> crate::Result<Box<dyn Algorithm>> {
+    if algorithm_name.eq_ignore_ascii_case(ED25519) {
+        Ok(Box::new(AlgorithmEd25519))
+    } else if algorithm_name.eq_ignore_ascii_case(SECP256K1) {
+        Ok(Box::new(AlgorithmSecp256k1))
+    } else {
+        Err(UnknownAlgorithm(algorithm_name.to_string()))
     }
 }
 
 impl Algorithm for AlgorithmEd25519 {
-    fn sign(&self, message: &[u8], sec_key: &[u8]) -> Vec<u8> {
-        let key_pair = ed25519_dalek::Keypair::from_bytes(sec_key).unwrap();
+    fn sign(&self, message: &[u8], sec_key: &[u8]) -> crate::Result<Vec<u8>> {
+        self.check(sec_key)?;
+        let key_pair = ed25519_dalek::Keypair::from_bytes(sec_key).map_err(|_| ErrorEd25519)?;
         let signature = key_pair.sign(message);
-        signature.to_bytes().to_vec()
-        // unimplemented!()
+        Ok(signature.to_bytes().to_vec())
     }
 
-    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> bool {
-        let public_key = ed25519_dalek::PublicKey::from_bytes(pub_key).unwrap();
-        let sig = Signature::try_from(signature).unwrap();
-        public_key.verify(message, &sig).is_ok()
-        // unimplemented!()
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> crate::Result<()> {
+        let public_key = ed25519_dalek::PublicKey::from_bytes(pub_key).map_err(|_| ErrorEd25519)?;
+        let sig = Signature::try_from(signature).map_err(|_| ErrorEd25519)?;
+        public_key.verify(message, &sig).map_err(|_| VerifyFailed)
     }
 
     #[cfg(feature = "std")]
     fn gen_sec_key(&self) -> Vec<u8> {
-        // let mut csprng = OsRng {};
-        // let secret_key: ed25519_dalek::SecretKey = ed25519_dalek::SecretKey::generate(&mut csprng);
-        // Vec::from(secret_key.as_ref())
-        unimplemented!()
+        let mut csprng = OsRng {};
+        let key_pair = ed25519_dalek::Keypair::generate(&mut csprng);
+        key_pair.to_bytes().to_vec()
     }
 
     fn get_pub_key(&self, sec_key: &[u8]) -> crate::Result<Vec<u8>> {
-        let key_pair = ed25519_dalek::Keypair::from_bytes(sec_key).unwrap();
+        self.check(sec_key)?;
+        let key_pair = ed25519_dalek::Keypair::from_bytes(sec_key).map_err(|_| ErrorEd25519)?;
         Ok(Vec::from(key_pair.public.as_ref()))
-        // unimplemented!()
     }
 
     fn check(&self, sec_key: &[u8]) -> crate::Result<()> {
-        unimplemented!()
+        if sec_key.len() != ed25519_dalek::KEYPAIR_LENGTH {
+            return Err(InvalidSecretKeyLength(sec_key.len()));
+        }
+        let secret =
+            ed25519_dalek::SecretKey::from_bytes(&sec_key[..ed25519_dalek::SECRET_KEY_LENGTH])
+                .map_err(|_| InvalidSecretKey)?;
+        let public = ed25519_dalek::PublicKey::from(&secret);
+        if public.as_bytes() != &sec_key[ed25519_dalek::SECRET_KEY_LENGTH..] {
+            return Err(PublicKeyMismatch);
+        }
+        Ok(())
     }
 }
 
-impl Algorithm for AlgorithmSecp256k1 {
-    fn sign(&self, message: &[u8], sec_key: &[u8]) -> Vec<u8> {
-        let msg = secp256k1::Message::parse_slice(message).unwrap();
-        let secret_key = secp256k1::SecretKey::parse_slice(sec_key).unwrap();
+impl AlgorithmSecp256k1 {
+    /// Signs `message` like `sign`, keeping the recovery id
+    pub fn sign_recoverable(&self, message: &[u8], sec_key: &[u8]) -> Result<signature::Signature> {
+        self.check(sec_key)?;
+        signature::Signature::sign(message, sec_key)
+    }
 
-        let (sig, recv_id) = secp256k1::sign(&msg, &secret_key);
-        sig.serialize().to_vec()
+    /// Public key of the signer of `message`, from its 65 bytes recoverable `signature`
+    pub fn recover_pub_key(&self, message: &[u8], signature: &[u8]) -> Result<PublicKey> {
+        signature::recover_pub_key(message, signature)
     }
+}
 
-    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> bool {
-        let msg = secp256k1::Message::parse_slice(message).unwrap();
-        let sig = secp256k1::Signature::parse_slice(signature).unwrap();
-        let public_key = secp256k1::PublicKey::parse_slice(pub_key, None).unwrap();
-        secp256k1::verify(&msg, &sig, &public_key)
+impl Algorithm for AlgorithmSecp256k1 {
+    fn sign(&self, message: &[u8], sec_key: &[u8]) -> Result<Vec<u8>> {
+        self.check(sec_key)?;
+        let signature = signature::Signature::sign(message, sec_key)?;
+        Ok(signature.sig.serialize().to_vec())
+    }
+
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        let msg = secp256k1::Message::parse_slice(message)?;
+        let sig = secp256k1::Signature::parse_slice(signature)?;
+        // (R, n - S) verifies as well, only the low-S form is accepted
+        if sig.s.is_high() {
+            return Err(NonCanonicalSignature);
+        }
+        let public_key = secp256k1::PublicKey::parse_slice(pub_key, None)?;
+        if secp256k1::verify(&msg, &sig, &public_key) {
+            Ok(())
+        } else {
+            Err(VerifyFailed)
+        }
     }
 
     #[cfg(feature = "std")]
@@ -95,13 +132,17 @@ impl Algorithm for AlgorithmSecp256k1 {
     }
 
     fn get_pub_key(&self, sec_key: &[u8]) -> Result<Vec<u8>> {
-        let secret_key = secp256k1::SecretKey::parse_slice(sec_key).unwrap();
+        let secret_key = secp256k1::SecretKey::parse_slice(sec_key)?;
         let public_key = secp256k1::PublicKey::from_secret_key(&secret_key);
         Ok(public_key.serialize_compressed().to_vec())
     }
 
     fn check(&self, sec_key: &[u8]) -> Result<()> {
-        unimplemented!()
+        if sec_key.len() != secp256k1::util::SECRET_KEY_SIZE {
+            return Err(InvalidSecretKeyLength(sec_key.len()));
+        }
+        secp256k1::SecretKey::parse_slice(sec_key).map_err(|_| InvalidSecretKey)?;
+        Ok(())
     }
 }
 
@@ -117,7 +158,7 @@ mod test {
             ("1rANSfcRzr4HkhbUFZ7L1Zp69JZZHiDDq5v7dNSbbEqeU4jxy3fszV4HGiaLQEyqVpS1dKT9g7zCVRxBVzuiUzB", "6sNQa7PV2SFzqCBtQUcQYJGGoU7XaB6R4xuCQVXNZe6b"),
         ];
 
-        let ed25519 = super::new(ED25519);
+        let ed25519 = super::new(ED25519).unwrap();
 
         for (hashed_code, expected) in cases {
             let sk = bs58::decode(hashed_code).into_vec().unwrap();
@@ -131,7 +172,7 @@ mod test {
             "lDS+SdM+aiVHbDyXapvrsgyKxFg9mJuHWPZb/INBRWY=",
             base64::encode(to_encode_pub_key)
         );
-        let secp256k1 = super::new(SECP256K1);
+        let secp256k1 = super::new(SECP256K1).unwrap();
 
         let sk = bs58::decode("3BZ3HWs2nWucCCvLp7FRFv1K7RR3fAjjEQccf9EJrTv4")
             .into_vec()
@@ -143,4 +184,71 @@ mod test {
             result
         );
     }
+
+    #[test]
+    fn check_should_report_bad_keys() {
+        let ed25519 = super::new(ED25519).unwrap();
+        let sk = bs58::decode(ROOT_KEY).into_vec().unwrap();
+        assert_eq!(ed25519.check(&sk), Ok(()));
+        assert_eq!(ed25519.check(&sk[..32]), Err(InvalidSecretKeyLength(32)));
+        let mut mismatched = sk.clone();
+        mismatched[40] ^= 1;
+        assert_eq!(ed25519.check(&mismatched), Err(PublicKeyMismatch));
+        assert_eq!(
+            ed25519.sign(b"message", &mismatched),
+            Err(PublicKeyMismatch)
+        );
+        assert_eq!(ed25519.get_pub_key(&mismatched), Err(PublicKeyMismatch));
+        assert!(ed25519.sign(b"message", &sk).is_ok());
+
+        let secp256k1 = super::new(SECP256K1).unwrap();
+        let sk = bs58::decode("3BZ3HWs2nWucCCvLp7FRFv1K7RR3fAjjEQccf9EJrTv4")
+            .into_vec()
+            .unwrap();
+        assert_eq!(secp256k1.check(&sk), Ok(()));
+        assert_eq!(secp256k1.check(&[1u8; 33]), Err(InvalidSecretKeyLength(33)));
+        assert_eq!(secp256k1.check(&[0u8; 32]), Err(InvalidSecretKey));
+        assert_eq!(secp256k1.check(&[0xffu8; 32]), Err(InvalidSecretKey));
+        assert_eq!(
+            secp256k1.sign(&[1u8; 32], &[0u8; 32]),
+            Err(InvalidSecretKey)
+        );
+        assert!(secp256k1.sign(b"not 32 bytes", &sk).is_err());
+        assert!(secp256k1.sign(&[1u8; 32], &sk).is_ok());
+    }
+
+    #[test]
+    fn algorithm_should_not_panic_on_bad_input() {
+        assert_eq!(
+            super::new("RSA").err(),
+            Some(UnknownAlgorithm("RSA".to_string()))
+        );
+        assert!(super::new("ed25519").is_ok());
+
+        let message = [1u8; 32];
+        for name in [ED25519, SECP256K1].iter() {
+            let algorithm = super::new(name).unwrap();
+            assert!(algorithm.get_pub_key(&[0u8; 3]).is_err());
+            assert!(algorithm.verify(&message, &[0u8; 3], &[0u8; 64]).is_err());
+        }
+
+        let ed25519 = super::new(ED25519).unwrap();
+        let sk = bs58::decode(ROOT_KEY).into_vec().unwrap();
+        let pk = ed25519.get_pub_key(&sk).unwrap();
+        let mut sig = ed25519.sign(&message, &sk).unwrap();
+        assert_eq!(ed25519.verify(&message, &pk, &sig), Ok(()));
+        assert!(ed25519.verify(&message, &pk, &sig[..10]).is_err());
+        sig[0] ^= 1;
+        assert_eq!(ed25519.verify(&message, &pk, &sig), Err(VerifyFailed));
+
+        let secp256k1 = super::new(SECP256K1).unwrap();
+        let sk = bs58::decode("3BZ3HWs2nWucCCvLp7FRFv1K7RR3fAjjEQccf9EJrTv4")
+            .into_vec()
+            .unwrap();
+        let pk = secp256k1.get_pub_key(&sk).unwrap();
+        let sig = secp256k1.sign(&message, &sk).unwrap();
+        assert_eq!(secp256k1.verify(&message, &pk, &sig), Ok(()));
+        assert!(secp256k1.verify(b"short", &pk, &sig).is_err());
+        assert_eq!(secp256k1.verify(&[2u8; 32], &pk, &sig), Err(VerifyFailed));
+    }
 }
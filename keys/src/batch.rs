@@ -0,0 +1,148 @@
+use alloc::boxed::Box;
+#[cfg(feature = "batch")]
+use alloc::vec::Vec;
+
+use crate::error::Error::BatchVerifyFailed;
+use crate::public::PublicKey;
+use crate::Result;
+
+/// A signature to verify: the signed message, the public key of the signer and the signature
+pub type BatchItem<'a> = (&'a [u8], PublicKey, &'a [u8]);
+
+/// Verifies all `items`, failing with `BatchVerifyFailed` and the index of the first item whose
+/// signature is malformed or does not match.
+///
+/// With the `batch` feature, ed25519 signatures are checked together, which is several times
+/// faster than checking them one by one. Items are only checked one by one to find the failing
+/// one.
+pub fn verify_batch(items: &[BatchItem]) -> Result<()> {
+    #[cfg(feature = "batch")]
+    {
+        if verify_ed25519_batch(items) {
+            return verify_each(
+                items
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, public_key, _))| !matches!(public_key, PublicKey::Ed25519(_))),
+            );
+        }
+    }
+    verify_each(items.iter().enumerate())
+}
+
+fn verify_each<'a, 'b: 'a, I>(items: I) -> Result<()>
+where
+    I: Iterator<Item = (usize, &'a BatchItem<'b>)>,
+{
+    for (index, (message, public_key, signature)) in items {
+        public_key
+            .verify(message, signature)
+            .map_err(|e| BatchVerifyFailed(index, Box::new(e)))?;
+    }
+    Ok(())
+}
+
+/// Whether all ed25519 signatures of `items` are well formed and match
+#[cfg(feature = "batch")]
+fn verify_ed25519_batch(items: &[BatchItem]) -> bool {
+    use core::convert::TryFrom;
+
+    let mut messages = Vec::new();
+    let mut signatures = Vec::new();
+    let mut public_keys = Vec::new();
+    for (message, public_key, signature) in items {
+        if let PublicKey::Ed25519(key) = public_key {
+            match (
+                ed25519_dalek::PublicKey::from_bytes(key),
+                ed25519_dalek::Signature::try_from(*signature),
+            ) {
+                (Ok(key), Ok(signature)) => {
+                    messages.push(*message);
+                    signatures.push(signature);
+                    public_keys.push(key);
+                }
+                _ => return false,
+            }
+        }
+    }
+    ed25519_dalek::verify_batch(&messages, &signatures, &public_keys).is_ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::Error::{NonCanonicalSignature, VerifyFailed};
+    use crate::keypair::KeyPair;
+    use alloc::vec::Vec;
+
+    fn signed(key_pair: &KeyPair, count: u8) -> Vec<([u8; 32], Vec<u8>)> {
+        (0..count)
+            .map(|i| {
+                let message = [i; 32];
+                (message, key_pair.sign(&message).unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn verify_batch_should_accept_valid_signatures() {
+        let ed25519: KeyPair = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".parse().unwrap();
+        let secp256k1: KeyPair = "xjggJ3TrLXz7qEwrGG3Rc4Fz59imjixhXpViq9W7Ncx"
+            .parse()
+            .unwrap();
+        let ed25519_signed = signed(&ed25519, 20);
+        let secp256k1_signed = signed(&secp256k1, 3);
+
+        let mut items: Vec<BatchItem> = ed25519_signed
+            .iter()
+            .map(|(m, s)| (&m[..], *ed25519.public(), &s[..]))
+            .collect();
+        items.extend(
+            secp256k1_signed
+                .iter()
+                .map(|(m, s)| (&m[..], *secp256k1.public(), &s[..])),
+        );
+        assert!(verify_batch(&items).is_ok());
+        assert!(verify_batch(&[]).is_ok());
+    }
+
+    #[test]
+    fn verify_batch_should_report_the_failing_item() {
+        let ed25519: KeyPair = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".parse().unwrap();
+        let secp256k1: KeyPair = "xjggJ3TrLXz7qEwrGG3Rc4Fz59imjixhXpViq9W7Ncx"
+            .parse()
+            .unwrap();
+        let ed25519_signed = signed(&ed25519, 8);
+        let mut items: Vec<BatchItem> = ed25519_signed
+            .iter()
+            .map(|(m, s)| (&m[..], *ed25519.public(), &s[..]))
+            .collect();
+
+        // signature of another message
+        items[5].0 = &ed25519_signed[4].0;
+        assert_eq!(
+            verify_batch(&items),
+            Err(BatchVerifyFailed(5, Box::new(VerifyFailed)))
+        );
+
+        items[5].0 = &ed25519_signed[5].0;
+        let truncated = &ed25519_signed[2].1[..10];
+        items[2].2 = truncated;
+        assert!(matches!(verify_batch(&items), Err(BatchVerifyFailed(2, _))));
+
+        // malleated secp256k1 signature
+        items[2].2 = &ed25519_signed[2].1;
+        let message = [7u8; 32];
+        let mut signature =
+            crate::signature::Signature::sign(&message, secp256k1.secret().as_bytes())
+                .unwrap()
+                .sig;
+        signature.s = -signature.s.clone();
+        let high_s = signature.serialize();
+        items.push((&message, *secp256k1.public(), &high_s));
+        assert_eq!(
+            verify_batch(&items),
+            Err(BatchVerifyFailed(8, Box::new(NonCanonicalSignature)))
+        );
+    }
+}
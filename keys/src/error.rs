@@ -1,5 +1,6 @@
 use crate::base58;
-use alloc::string::ToString;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
 use core::fmt;
 
 pub type Result<T> = core::result::Result<T, Error>;
@@ -13,10 +14,52 @@ pub enum Error {
 
     ErrorEd25519,
     ErrorSecp256k1,
+    /// secret key of the wrong length
+    InvalidSecretKeyLength(usize),
+    /// secret key out of the range of the curve
+    InvalidSecretKey,
+    /// public half of an ed25519 secret key not matching the secret half
+    PublicKeyMismatch,
+    /// public key of the wrong length
+    InvalidPublicKeyLength(usize),
+    /// Base64 encoding error
+    InvalidBase64,
+    /// signature of the wrong length
+    InvalidSignatureLength(usize),
+    /// high-S secp256k1 signature, the malleable form of a valid signature
+    NonCanonicalSignature,
+    /// keystore file not readable or writable
+    #[cfg(feature = "std")]
+    KeystoreIo(String),
+    /// remote signer not reachable or failing to sign
+    #[cfg(feature = "std")]
+    RemoteSigner(String),
+    /// keystore file not in the keystore format
+    InvalidKeystore(String),
+    /// password not decrypting the key
+    WrongPassword,
+    /// no key for the account and permission
+    KeyNotFound(String, String),
+    /// a key is already stored for the account and permission
+    KeyExists(String, String),
+    /// mnemonic word not in the wordlist
+    InvalidMnemonicWord(String),
+    /// mnemonic of the wrong number of words
+    InvalidMnemonicLength(usize),
+    /// mnemonic checksum not matching its words
+    InvalidMnemonicChecksum,
+    /// mnemonic entropy of the wrong length
+    InvalidEntropyLength(usize),
+    /// derivation path not parsable or not derivable
+    InvalidDerivationPath(String),
     /// hash error
     Hash(bitcoin_hashes::error::Error),
     /// verify failed
     VerifyFailed,
+    /// signature of the item at this index of a batch malformed or not matching
+    BatchVerifyFailed(usize, Box<Error>),
+    /// algorithm other than ED25519 and SECP256K1
+    UnknownAlgorithm(String),
 }
 
 impl fmt::Display for Error {
@@ -27,7 +70,35 @@ impl fmt::Display for Error {
             Error::ErrorEd25519 => f.write_str("Ed25519 failed"),
             Error::Hash(ref e) => f.write_str(&e.to_string()),
             Error::VerifyFailed => f.write_str("Verify failed"),
+            Error::BatchVerifyFailed(index, ref e) => {
+                write!(f, "Batch verify failed at item {}: {}", index, e)
+            }
+            Error::UnknownAlgorithm(ref name) => write!(f, "Unknown algorithm {}", name),
             Error::ErrorSecp256k1 => f.write_str("Secp256k1 failed"),
+            Error::InvalidSecretKeyLength(len) => write!(f, "Invalid secret key length {}", len),
+            Error::InvalidSecretKey => f.write_str("Invalid secret key"),
+            Error::PublicKeyMismatch => f.write_str("Public key mismatch"),
+            Error::InvalidPublicKeyLength(len) => write!(f, "Invalid public key length {}", len),
+            Error::InvalidBase64 => f.write_str("Invalid base64"),
+            Error::InvalidSignatureLength(len) => write!(f, "Invalid signature length {}", len),
+            Error::NonCanonicalSignature => f.write_str("Non canonical signature"),
+            #[cfg(feature = "std")]
+            Error::KeystoreIo(ref e) => write!(f, "Keystore io error {}", e),
+            #[cfg(feature = "std")]
+            Error::RemoteSigner(ref e) => write!(f, "Remote signer error {}", e),
+            Error::InvalidKeystore(ref e) => write!(f, "Invalid keystore {}", e),
+            Error::WrongPassword => f.write_str("Wrong password"),
+            Error::KeyNotFound(ref account, ref permission) => {
+                write!(f, "No key for {}@{}", account, permission)
+            }
+            Error::KeyExists(ref account, ref permission) => {
+                write!(f, "Key already stored for {}@{}", account, permission)
+            }
+            Error::InvalidMnemonicWord(ref word) => write!(f, "Invalid mnemonic word {}", word),
+            Error::InvalidMnemonicLength(len) => write!(f, "Invalid mnemonic length {}", len),
+            Error::InvalidMnemonicChecksum => f.write_str("Invalid mnemonic checksum"),
+            Error::InvalidEntropyLength(len) => write!(f, "Invalid entropy length {}", len),
+            Error::InvalidDerivationPath(ref path) => write!(f, "Invalid derivation path {}", path),
         }
     }
 }
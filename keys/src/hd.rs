@@ -0,0 +1,351 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
+
+use bitcoin_hashes::{hmac, sha512, Hash as HashTrait, HashEngine};
+use zeroize::Zeroize;
+
+use crate::error::Error::{InvalidDerivationPath, InvalidSecretKey, UnknownAlgorithm};
+use crate::keypair::KeyPair;
+use crate::{algorithm, error, Result};
+
+/// Offset of hardened child indexes
+pub const HARDENED: u32 = 0x8000_0000;
+/// SLIP-44 coin type of IOST
+pub const IOST_COIN_TYPE: u32 = 291;
+
+/// A BIP-32 derivation path, such as `m/44'/291'/0'`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// `m/44'/291'/index'`, hardened all the way so that it derives ED25519 keys as well
+    pub fn iost(index: u32) -> Self {
+        DerivationPath(vec![
+            44 | HARDENED,
+            IOST_COIN_TYPE | HARDENED,
+            index | HARDENED,
+        ])
+    }
+
+    pub fn indexes(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = error::Error;
+
+    /// Parse a path such as `m/44'/291'/0'`. Hardened indexes end with `'` or `h`
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.split('/');
+        if parts.next() != Some("m") {
+            return Err(InvalidDerivationPath(s.to_string()));
+        }
+        parts
+            .map(|part| {
+                let (index, hardened) = match part.strip_suffix(|c| c == '\'' || c == 'h') {
+                    Some(index) => (index, HARDENED),
+                    None => (part, 0),
+                };
+                match index.parse::<u32>() {
+                    Ok(index) if index < HARDENED => Ok(index | hardened),
+                    _ => Err(InvalidDerivationPath(s.to_string())),
+                }
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(DerivationPath)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("m")?;
+        for index in &self.0 {
+            if index & HARDENED != 0 {
+                write!(f, "/{}'", index & !HARDENED)?;
+            } else {
+                write!(f, "/{}", index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut engine = hmac::HmacEngine::<sha512::Hash>::new(key);
+    for item in data {
+        engine.input(item);
+    }
+    hmac::Hmac::<sha512::Hash>::from_engine(engine).into_inner()
+}
+
+/// An extended private key: SLIP-10 for ED25519 and BIP-32 for SECP256K1. Wiped from memory when
+/// dropped
+#[derive(Clone)]
+pub struct ExtendedKey {
+    algorithm: &'static str,
+    key: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+impl ExtendedKey {
+    /// Master key of `seed`, such as the seed of a `Mnemonic`
+    pub fn master(algorithm_name: &str, seed: &[u8]) -> Result<Self> {
+        let (algorithm, curve) = if algorithm_name.eq_ignore_ascii_case(algorithm::ED25519) {
+            (algorithm::ED25519, "ed25519 seed")
+        } else if algorithm_name.eq_ignore_ascii_case(algorithm::SECP256K1) {
+            (algorithm::SECP256K1, "Bitcoin seed")
+        } else {
+            return Err(UnknownAlgorithm(String::from(algorithm_name)));
+        };
+        let extended = ExtendedKey::from_hmac(algorithm, hmac_sha512(curve.as_bytes(), &[seed]));
+        if algorithm == algorithm::SECP256K1 {
+            secp256k1::SecretKey::parse(&extended.key).map_err(|_| InvalidSecretKey)?;
+        }
+        Ok(extended)
+    }
+
+    fn from_hmac(algorithm: &'static str, mut i: [u8; 64]) -> Self {
+        let mut extended = ExtendedKey {
+            algorithm,
+            key: [0u8; 32],
+            chain_code: [0u8; 32],
+        };
+        extended.key.copy_from_slice(&i[..32]);
+        extended.chain_code.copy_from_slice(&i[32..]);
+        i.zeroize();
+        extended
+    }
+
+    /// Child key at `index`. ED25519 only derives hardened children
+    pub fn derive_child(&self, index: u32) -> Result<Self> {
+        let index_bytes = index.to_be_bytes();
+        if self.algorithm == algorithm::ED25519 {
+            if index & HARDENED == 0 {
+                return Err(InvalidDerivationPath(format!(
+                    "ED25519 only derives hardened keys, not {}",
+                    index
+                )));
+            }
+            let i = hmac_sha512(&self.chain_code, &[&[0], &self.key, &index_bytes]);
+            return Ok(ExtendedKey::from_hmac(self.algorithm, i));
+        }
+
+        let secret = secp256k1::SecretKey::parse(&self.key).map_err(|_| InvalidSecretKey)?;
+        let i = if index & HARDENED != 0 {
+            hmac_sha512(&self.chain_code, &[&[0], &self.key, &index_bytes])
+        } else {
+            let public = secp256k1::PublicKey::from_secret_key(&secret).serialize_compressed();
+            hmac_sha512(&self.chain_code, &[&public, &index_bytes])
+        };
+        let mut child = ExtendedKey::from_hmac(self.algorithm, i);
+        // the child key is the parent key tweaked by the left half of the HMAC
+        let tweak = secp256k1::SecretKey::parse(&child.key).map_err(|_| InvalidSecretKey)?;
+        let mut key = secret;
+        key.tweak_add_assign(&tweak).map_err(|_| InvalidSecretKey)?;
+        child.key = key.serialize();
+        Ok(child)
+    }
+
+    pub fn derive(&self, path: &DerivationPath) -> Result<Self> {
+        path.indexes()
+            .iter()
+            .try_fold(self.clone(), |key, index| key.derive_child(*index))
+    }
+
+    pub fn algorithm(&self) -> &'static str {
+        self.algorithm
+    }
+
+    /// 32 bytes secret key, the ED25519 seed or the SECP256K1 secret key
+    pub fn secret_key(&self) -> &[u8; 32] {
+        &self.key
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    pub fn key_pair(&self) -> Result<KeyPair> {
+        KeyPair::from_secret_key(self.algorithm, &self.key)
+    }
+}
+
+impl Drop for ExtendedKey {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.chain_code.zeroize();
+    }
+}
+
+impl fmt::Debug for ExtendedKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ExtendedKey({}, ..)", self.algorithm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mnemonic::Mnemonic;
+
+    /// Checks the chain code and secret key of each path, as published in the test vectors
+    fn check_vectors(algorithm_name: &str, seed: &str, cases: &[(&str, &str, &str)]) {
+        let master = ExtendedKey::master(algorithm_name, &hex::decode(seed).unwrap()).unwrap();
+        for (path, chain_code, secret_key) in cases {
+            let key = master.derive(&path.parse().unwrap()).unwrap();
+            assert_eq!(hex::encode(key.chain_code()), *chain_code, "{}", path);
+            assert_eq!(hex::encode(key.secret_key()), *secret_key, "{}", path);
+        }
+    }
+
+    #[test]
+    fn slip10_ed25519_vectors() {
+        check_vectors(
+            algorithm::ED25519,
+            "000102030405060708090a0b0c0d0e0f",
+            &[
+                (
+                    "m",
+                    "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+                    "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+                ),
+                (
+                    "m/0'",
+                    "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+                    "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+                ),
+                (
+                    "m/0'/1'",
+                    "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+                    "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+                ),
+                (
+                    "m/0'/1'/2'",
+                    "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+                    "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+                ),
+                (
+                    "m/0'/1'/2'/2'",
+                    "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc",
+                    "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+                ),
+                (
+                    "m/0'/1'/2'/2'/1000000000'",
+                    "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+                    "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+                ),
+            ],
+        );
+
+        let master = ExtendedKey::master(
+            algorithm::ED25519,
+            &hex::decode("000102030405060708090a0b0c0d0e0f").unwrap(),
+        )
+        .unwrap();
+        // public keys of the vectors, prefixed with 00 in SLIP-10
+        assert_eq!(
+            hex::encode(master.key_pair().unwrap().public().as_bytes()),
+            "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed"
+        );
+        let child = master.derive(&"m/0'/1'".parse().unwrap()).unwrap();
+        assert_eq!(
+            hex::encode(child.key_pair().unwrap().public().as_bytes()),
+            "1932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187"
+        );
+        assert!(master.derive_child(0).is_err());
+    }
+
+    #[test]
+    fn bip32_secp256k1_vectors() {
+        check_vectors(
+            algorithm::SECP256K1,
+            "000102030405060708090a0b0c0d0e0f",
+            &[
+                (
+                    "m",
+                    "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+                    "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+                ),
+                (
+                    "m/0'",
+                    "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+                    "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+                ),
+                (
+                    "m/0'/1",
+                    "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+                    "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+                ),
+                (
+                    "m/0'/1/2'",
+                    "04466b9cc8e161e966409ca52986c584f07e9dc81f735db683c3ff6ec7b1503f",
+                    "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+                ),
+                (
+                    "m/0'/1/2'/2",
+                    "cfb71883f01676f587d023cc53a35bc7f88f724b1f8c2892ac1275ac822a3edd",
+                    "0f479245fb19a38a1954c5c7c0ebab2f9bdfd96a17563ef28a6a4b1a2a764ef4",
+                ),
+                (
+                    "m/0'/1/2'/2/1000000000",
+                    "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e",
+                    "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn derivation_path_should_parse_and_display() {
+        let path: DerivationPath = "m/44'/291'/0h/1".parse().unwrap();
+        assert_eq!(
+            path.indexes(),
+            &[44 | HARDENED, 291 | HARDENED, HARDENED, 1]
+        );
+        assert_eq!(path.to_string(), "m/44'/291'/0'/1");
+        assert_eq!(DerivationPath::iost(0).to_string(), "m/44'/291'/0'");
+        assert!("m".parse::<DerivationPath>().unwrap().indexes().is_empty());
+        for invalid in ["", "44'/0", "m/x", "m/2147483648", "m//1"].iter() {
+            assert_eq!(
+                invalid.parse::<DerivationPath>(),
+                Err(InvalidDerivationPath(invalid.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn mnemonic_should_derive_iost_key_pairs() {
+        let mnemonic: Mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".parse().unwrap();
+        let seed = mnemonic.to_seed("");
+        for algorithm_name in [algorithm::ED25519, algorithm::SECP256K1].iter() {
+            let master = ExtendedKey::master(algorithm_name, &seed).unwrap();
+            let key_pair = master
+                .derive(&DerivationPath::iost(0))
+                .unwrap()
+                .key_pair()
+                .unwrap();
+            assert_eq!(key_pair.algorithm(), *algorithm_name);
+
+            // usable by the algorithms of the chain
+            let algorithm = algorithm::new(algorithm_name).unwrap();
+            let message = [9u8; 32];
+            let signature = algorithm
+                .sign(&message, key_pair.secret().as_bytes())
+                .unwrap();
+            assert_eq!(
+                algorithm.get_pub_key(key_pair.secret().as_bytes()).unwrap(),
+                key_pair.public().to_bytes()
+            );
+            assert!(algorithm
+                .verify(&message, key_pair.public().as_bytes(), &signature)
+                .is_ok());
+
+            let other = master.derive(&DerivationPath::iost(1)).unwrap();
+            assert!(other.key_pair().unwrap() != key_pair);
+        }
+    }
+}
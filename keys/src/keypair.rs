@@ -1,126 +1,165 @@
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::str::FromStr;
 
-use crate::{algorithm, Result};
-// use crate::public::PublicKey;
-// use crate::secret::SecretKey;
-// use crate::signature::Signature;
-use rand::Rng;
+use crate::public::PublicKey;
+use crate::secret::SecretKey;
+use crate::{error, Result};
 
-/// A secp256k1 keypair.
-#[derive(Clone, PartialEq, Eq)]
+/// A secp256k1 or ed25519 keypair.
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct KeyPair {
-    pub algorithm: String,
     /// The secret half of this keypair.
-    pub sk: Vec<u8>,
+    sk: SecretKey,
     /// The public half of this keypair.
-    pub pk: Vec<u8>,
+    pk: PublicKey,
 }
 
 impl KeyPair {
-    // pub fn new(algorithm_name: &str) -> Result<KeyPair> {
-    //     let algorithm = algorithm::new(algorithm_name);
-    //     let sk = algorithm.gen_sec_key();
-    //     let pk = algorithm.get_pub_key(sk.as_ref()).unwrap();
-    //     Ok(KeyPair {
-    //         algorithm: String::from(algorithm_name),
-    //         sk,
-    //         pk,
-    //     })
-    // }
-    //
-    // pub fn sign(&self, message: &[u8], sec_key: &[u8]) -> Vec<u8> {
-    //     let algorithm = algorithm::new(self.algorithm.as_str());
-    //     algorithm.sign(message, sec_key)
-    // }
+    /// Generate a keypair for `algorithm_name`, `ED25519` or `SECP256K1`.
+    #[cfg(feature = "std")]
+    pub fn new(algorithm_name: &str) -> Result<KeyPair> {
+        Ok(KeyPair::from(SecretKey::generate(algorithm_name)?))
+    }
+
+    /// Import a keypair from its secret key. For ed25519, the 32 bytes seed is accepted as well
+    /// as the 64 bytes secret key of iwallet.
+    pub fn from_secret_key(algorithm_name: &str, sec_key: &[u8]) -> Result<KeyPair> {
+        Ok(KeyPair::from(SecretKey::from_bytes(
+            algorithm_name,
+            sec_key,
+        )?))
+    }
+
+    /// Import a keypair from its base58 secret key, as exported by iwallet.
+    pub fn from_secret_base58(algorithm_name: &str, sec_key: &str) -> Result<KeyPair> {
+        Ok(KeyPair::from(SecretKey::from_base58(
+            algorithm_name,
+            sec_key,
+        )?))
+    }
+
+    /// Name of the algorithm of this keypair, `ED25519` or `SECP256K1`.
+    pub fn algorithm(&self) -> &'static str {
+        self.sk.algorithm()
+    }
+
+    pub fn secret(&self) -> &SecretKey {
+        &self.sk
+    }
+
+    pub fn public(&self) -> &PublicKey {
+        &self.pk
+    }
+
+    /// Base58 secret key, as imported by iwallet.
+    pub fn secret_base58(&self) -> String {
+        self.sk.to_base58()
+    }
+
+    /// Base58 public key, as shown by iwallet.
+    pub fn public_base58(&self) -> String {
+        self.pk.to_base58()
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.sk.sign(message)
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        self.pk.verify(message, signature)
+    }
+}
+
+impl From<SecretKey> for KeyPair {
+    fn from(sk: SecretKey) -> Self {
+        KeyPair {
+            pk: sk.public_key(),
+            sk,
+        }
+    }
 }
 
-// impl Keypair {
-//     /// Generate an secp256k1 keypair.
-//     pub fn generate<R>(csprng: &mut R) -> Keypair
-//     where
-//         R: Rng,
-//     {
-//         let sk = SecretKey::generate(csprng);
-//         let pk = PublicKey::from(&sk);
-//
-//         Keypair { sk, pk }
-//     }
-//
-//     /// Generate an secp256k1 keypair from secret in WIF format
-//     pub fn from_secret_wif(wif: &str) -> crate::Result<Keypair> {
-//         let sk = SecretKey::from_wif(wif)?;
-//         let pk = PublicKey::from(&sk);
-//
-//         Ok(Keypair { sk, pk })
-//     }
-//
-//     /// Convert this keypair to bytes.
-//     pub fn to_bytes(&self) -> [u8; KEYPAIR_LENGTH] {
-//         let mut bytes: [u8; KEYPAIR_LENGTH] = [0u8; KEYPAIR_LENGTH];
-//
-//         bytes[..SECRET_KEY_SIZE].copy_from_slice(self.sk.to_bytes().as_slice());
-//         bytes[SECRET_KEY_SIZE..].copy_from_slice(self.pk.to_bytes().as_slice());
-//
-//         bytes
-//     }
-//
-//     /// Sign a message with this keypair's secret key.
-//     pub fn sign(&self, message: &[u8]) -> crate::Result<Signature> {
-//         self.sk.sign(&message)
-//     }
-//
-//     /// Verify a signature on a message with this keypair's public key
-//     pub fn verify(&self, message: &[u8], signature: &Signature) -> crate::Result<()> {
-//         self.pk.verify(&message, &signature)
-//     }
-// }
-//
-// #[cfg(test)]
-// mod tests {
-//     use super::Keypair;
-//     use super::PublicKey;
-//     use alloc::string::ToString;
-//     #[cfg(feature = "std")]
-//     use rand::thread_rng;
-//
-//     #[cfg(feature = "std")]
-//     #[test]
-//     fn keypair_generate_should_work() {
-//         let mut rng = thread_rng();
-//         let keypair = Keypair::generate(&mut rng);
-//
-//         assert_eq!(PublicKey::from(&keypair.sk), keypair.pk);
-//     }
-//
-//     #[test]
-//     fn keypair_from_secret_wif_should_work() {
-//         let wif = "5HrBLKfeEdqH9KLMv1daHLVjrXV3DGVERAkN5cdSSc58bzqqfT4";
-//         let keypair = Keypair::from_secret_wif(wif).unwrap();
-//         assert_eq!(
-//             keypair.pk.to_string(),
-//             "EOS8FdQ4gt16pFcSiXAYCcHnkHTS2nNLFWGZXW5sioAdvQuMxKhAm"
-//         );
-//     }
-//
-//     #[test]
-//     fn keypair_sign_should_work() {
-//         let wif = "5HrBLKfeEdqH9KLMv1daHLVjrXV3DGVERAkN5cdSSc58bzqqfT4";
-//         let keypair = Keypair::from_secret_wif(wif).unwrap();
-//         let message = "hello".as_bytes();
-//         let sig = keypair.sign(&message);
-//         assert!(sig.is_ok());
-//     }
-//
-//     #[test]
-//     fn keypair_verify_should_work() {
-//         let wif = "5HrBLKfeEdqH9KLMv1daHLVjrXV3DGVERAkN5cdSSc58bzqqfT4";
-//         let keypair = Keypair::from_secret_wif(wif).unwrap();
-//         let message = "hello".as_bytes();
-//         let sig = keypair.sign(&message);
-//         assert!(sig.is_ok());
-//
-//         let verify = keypair.verify(&message, &sig.unwrap());
-//         assert!(verify.is_ok());
-//     }
-// }
+impl FromStr for KeyPair {
+    type Err = error::Error;
+
+    /// Import a keypair from its base58 secret key, telling the algorithm from its length.
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(KeyPair::from(s.parse::<SecretKey>()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm;
+    use crate::error::Error::InvalidSecretKeyLength;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn keypair_new_should_work() {
+        for algorithm_name in [algorithm::ED25519, algorithm::SECP256K1].iter() {
+            let keypair = KeyPair::new(algorithm_name).unwrap();
+            let imported =
+                KeyPair::from_secret_base58(algorithm_name, &keypair.secret_base58()).unwrap();
+            assert!(imported == keypair);
+            assert_eq!(keypair.algorithm(), *algorithm_name);
+
+            let message = [7u8; 32];
+            let signature = keypair.sign(&message).unwrap();
+            assert!(imported.verify(&message, &signature).is_ok());
+        }
+        let keypair = KeyPair::new(algorithm::ED25519).unwrap();
+        assert_eq!(keypair.secret().as_bytes().len(), 64);
+        assert_eq!(
+            &keypair.secret().as_bytes()[32..],
+            keypair.public().as_bytes()
+        );
+        assert_eq!(
+            KeyPair::new(algorithm::SECP256K1)
+                .unwrap()
+                .public()
+                .as_bytes()
+                .len(),
+            33
+        );
+    }
+
+    #[test]
+    fn keypair_from_secret_base58_should_match_iwallet() {
+        let keypair = KeyPair::from_secret_base58(
+            algorithm::ED25519,
+            "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1",
+        )
+        .unwrap();
+        assert_eq!(
+            keypair.public_base58(),
+            "Gcv8c2tH8qZrUYnKdEEdTtASsxivic2834MQW6mgxqto"
+        );
+        assert_eq!(
+            keypair.secret_base58(),
+            "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1"
+        );
+        assert!(keypair.secret_base58().parse::<KeyPair>().unwrap() == keypair);
+
+        // the seed alone gives the same keypair
+        let seed = crate::base58::encode_slice(&keypair.secret().as_bytes()[..32]);
+        assert!(KeyPair::from_secret_base58(algorithm::ED25519, &seed).unwrap() == keypair);
+
+        let keypair = KeyPair::from_secret_base58(
+            algorithm::SECP256K1,
+            "3BZ3HWs2nWucCCvLp7FRFv1K7RR3fAjjEQccf9EJrTv4",
+        )
+        .unwrap();
+        assert_eq!(
+            keypair.public_base58(),
+            "iWgLQj3VTPN4dZnomuJMMCggv22LFw4nAkA6bmrVsmCo"
+        );
+
+        assert_eq!(
+            KeyPair::from_secret_base58(algorithm::ED25519, "3BZ3HWs2nWucCCvLp7FRFv1K7RR3").err(),
+            Some(InvalidSecretKeyLength(20))
+        );
+        assert!(KeyPair::from_secret_base58(algorithm::SECP256K1, "0OIl").is_err());
+    }
+}
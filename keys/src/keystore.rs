@@ -0,0 +1,529 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::error::Error::{InvalidKeystore, KeyExists, KeyNotFound, KeystoreIo, WrongPassword};
+use crate::keypair::KeyPair;
+use crate::public::PublicKey;
+use crate::secret::SecretKey;
+use crate::signer::Signer;
+use crate::Result;
+
+/// Version of the keystore file format
+pub const KEYSTORE_VERSION: u32 = 1;
+/// AEAD encrypting the secret keys
+pub const CIPHER: &str = "chacha20poly1305";
+
+const SALT_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+
+/// Largest scrypt `log_n` read from a keystore file
+const MAX_LOG_N: u8 = 20;
+/// Largest scrypt `r * p` read from a keystore file
+const MAX_R_P: u64 = 64;
+/// Largest scrypt memory, `128 * r * 2^log_n` bytes
+const MAX_MEMORY: u64 = 1 << 30;
+
+/// Parameters of the scrypt derivation of the encryption key from the password
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScryptParams {
+    /// log2 of the CPU/memory cost
+    pub log_n: u8,
+    /// block size
+    pub r: u32,
+    /// parallelization
+    pub p: u32,
+}
+
+impl Default for ScryptParams {
+    fn default() -> Self {
+        ScryptParams {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// A secret key encrypted with a password
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedKey {
+    /// `ED25519` or `SECP256K1`
+    pub algorithm: String,
+    /// Base58 public key, readable without the password
+    pub public_key: String,
+    pub kdf: ScryptParams,
+    /// Base64 salt of the key derivation
+    pub salt: String,
+    pub cipher: String,
+    /// Base64 nonce of the cipher
+    pub nonce: String,
+    /// Base64 encrypted secret key, followed by the authentication tag
+    pub ciphertext: String,
+}
+
+/// A key of the keystore, as listed without the password
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyInfo {
+    pub account: String,
+    pub permission: String,
+    pub algorithm: String,
+    pub public_key: String,
+}
+
+/// Password encrypted keys of IOST accounts, stored as JSON mapping account name to permission
+/// to key
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub accounts: BTreeMap<String, BTreeMap<String, EncryptedKey>>,
+    /// scrypt parameters of the keys encrypted from now on
+    #[serde(skip)]
+    kdf: ScryptParams,
+}
+
+impl Default for Keystore {
+    fn default() -> Self {
+        Keystore {
+            version: KEYSTORE_VERSION,
+            accounts: BTreeMap::new(),
+            kdf: ScryptParams::default(),
+        }
+    }
+}
+
+/// Binds the ciphertext to its entry, so that keys cannot be swapped between entries
+fn associated_data(account: &str, permission: &str, public_key: &str) -> Vec<u8> {
+    format!("{}@{}:{}", account, permission, public_key).into_bytes()
+}
+
+fn derive_key(password: &str, salt: &[u8], kdf: &ScryptParams) -> Result<[u8; 32]> {
+    // a crafted keystore must not make scrypt allocate more than it can
+    let (r, p) = (u64::from(kdf.r), u64::from(kdf.p));
+    if kdf.log_n > MAX_LOG_N || r * p > MAX_R_P || (128 * r) << kdf.log_n > MAX_MEMORY {
+        return Err(InvalidKeystore("scrypt parameters too large".to_string()));
+    }
+    let params = scrypt::ScryptParams::new(kdf.log_n, kdf.r, kdf.p)
+        .map_err(|_| InvalidKeystore("invalid scrypt parameters".to_string()))?;
+    let mut key = [0u8; 32];
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+        .map_err(|_| InvalidKeystore("invalid scrypt output length".to_string()))?;
+    Ok(key)
+}
+
+fn decode(field: &str, value: &str) -> Result<Vec<u8>> {
+    base64::decode(value).map_err(|_| InvalidKeystore(format!("invalid {}", field)))
+}
+
+impl EncryptedKey {
+    pub fn encrypt(
+        account: &str,
+        permission: &str,
+        key_pair: &KeyPair,
+        password: &str,
+        kdf: &ScryptParams,
+    ) -> Result<Self> {
+        let mut salt = [0u8; SALT_LENGTH];
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+
+        let public_key = key_pair.public_base58();
+        let mut key = derive_key(password, &salt, kdf)?;
+        let cipher = ChaCha20Poly1305::new(&Key::from(key));
+        key.zeroize();
+        let ciphertext = cipher
+            .encrypt(
+                &Nonce::from(nonce),
+                Payload {
+                    msg: key_pair.secret().as_bytes(),
+                    aad: &associated_data(account, permission, &public_key),
+                },
+            )
+            .map_err(|_| InvalidKeystore("encryption failed".to_string()))?;
+
+        Ok(EncryptedKey {
+            algorithm: key_pair.algorithm().to_string(),
+            public_key,
+            kdf: kdf.clone(),
+            salt: base64::encode(salt),
+            cipher: CIPHER.to_string(),
+            nonce: base64::encode(nonce),
+            ciphertext: base64::encode(ciphertext),
+        })
+    }
+
+    /// Decrypts the key of `account@permission`, failing with `WrongPassword` if the password or
+    /// the entry do not match
+    pub fn decrypt(&self, account: &str, permission: &str, password: &str) -> Result<KeyPair> {
+        if self.cipher != CIPHER {
+            return Err(InvalidKeystore(format!("unknown cipher {}", self.cipher)));
+        }
+        let salt = decode("salt", &self.salt)?;
+        let mut nonce = [0u8; NONCE_LENGTH];
+        match decode("nonce", &self.nonce)? {
+            bytes if bytes.len() == NONCE_LENGTH => nonce.copy_from_slice(&bytes),
+            _ => return Err(InvalidKeystore("invalid nonce".to_string())),
+        }
+        let ciphertext = decode("ciphertext", &self.ciphertext)?;
+
+        let mut key = derive_key(password, &salt, &self.kdf)?;
+        let cipher = ChaCha20Poly1305::new(&Key::from(key));
+        key.zeroize();
+        let mut secret = cipher
+            .decrypt(
+                &Nonce::from(nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &associated_data(account, permission, &self.public_key),
+                },
+            )
+            .map_err(|_| WrongPassword)?;
+        let secret_key = SecretKey::from_bytes(&self.algorithm, &secret);
+        secret.zeroize();
+
+        let key_pair = KeyPair::from(secret_key?);
+        if key_pair.public_base58() != self.public_key {
+            return Err(InvalidKeystore("public key mismatch".to_string()));
+        }
+        Ok(key_pair)
+    }
+}
+
+/// Unlocked key of a keystore, knowing the account and permission it signs for
+#[derive(Clone, Debug)]
+pub struct KeystoreSigner {
+    account: String,
+    permission: String,
+    key_pair: KeyPair,
+}
+
+impl KeystoreSigner {
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    pub fn permission(&self) -> &str {
+        &self.permission
+    }
+
+    /// `account@permission`, as listed in the signers of a transaction
+    pub fn signer_id(&self) -> String {
+        format!("{}@{}", self.account, self.permission)
+    }
+}
+
+impl Signer for KeystoreSigner {
+    fn public_key(&self) -> PublicKey {
+        self.key_pair.public_key()
+    }
+
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<Vec<u8>> {
+        self.key_pair.sign_digest(digest)
+    }
+}
+
+impl Keystore {
+    pub fn new() -> Self {
+        Keystore::default()
+    }
+
+    /// Use `kdf` for the keys encrypted from now on
+    pub fn with_kdf(mut self, kdf: ScryptParams) -> Self {
+        self.kdf = kdf;
+        self
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        let keystore: Keystore =
+            serde_json::from_str(json).map_err(|e| InvalidKeystore(e.to_string()))?;
+        if keystore.version != KEYSTORE_VERSION {
+            return Err(InvalidKeystore(format!(
+                "unsupported version {}",
+                keystore.version
+            )));
+        }
+        Ok(keystore)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| InvalidKeystore(e.to_string()))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let json = fs::read_to_string(path).map_err(|e| KeystoreIo(e.to_string()))?;
+        Keystore::from_json(&json)
+    }
+
+    /// Writes the keystore to `path`, through a temporary file so that a failed write leaves the
+    /// previous keystore intact. On unix, the file is readable by its owner only
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        let json = self.to_json()?;
+        // a stale temporary file would keep its permissions
+        let _ = fs::remove_file(&tmp);
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&tmp).map_err(|e| KeystoreIo(e.to_string()))?;
+        file.write_all(json.as_bytes())
+            .map_err(|e| KeystoreIo(e.to_string()))?;
+        fs::rename(&tmp, path).map_err(|e| KeystoreIo(e.to_string()))
+    }
+
+    /// Encrypts and stores the key of `account@permission`, failing with `KeyExists` if there is
+    /// one already. Use `rotate` to replace it
+    pub fn insert(
+        &mut self,
+        account: &str,
+        permission: &str,
+        key_pair: &KeyPair,
+        password: &str,
+    ) -> Result<()> {
+        if self.get(account, permission).is_ok() {
+            return Err(KeyExists(account.to_string(), permission.to_string()));
+        }
+        let key = EncryptedKey::encrypt(account, permission, key_pair, password, &self.kdf)?;
+        self.accounts
+            .entry(account.to_string())
+            .or_default()
+            .insert(permission.to_string(), key);
+        Ok(())
+    }
+
+    pub fn get(&self, account: &str, permission: &str) -> Result<&EncryptedKey> {
+        self.accounts
+            .get(account)
+            .and_then(|permissions| permissions.get(permission))
+            .ok_or_else(|| KeyNotFound(account.to_string(), permission.to_string()))
+    }
+
+    /// Decrypts the key of `account@permission`, ready to sign transactions with `Tx::sign`
+    pub fn unlock(&self, account: &str, permission: &str, password: &str) -> Result<KeyPair> {
+        self.get(account, permission)?
+            .decrypt(account, permission, password)
+    }
+
+    /// Unlocks the key of `account@permission` as a `Signer`
+    pub fn signer(
+        &self,
+        account: &str,
+        permission: &str,
+        password: &str,
+    ) -> Result<KeystoreSigner> {
+        Ok(KeystoreSigner {
+            account: account.to_string(),
+            permission: permission.to_string(),
+            key_pair: self.unlock(account, permission, password)?,
+        })
+    }
+
+    /// Keys of the keystore, by account then permission
+    pub fn list(&self) -> Vec<KeyInfo> {
+        self.accounts
+            .iter()
+            .flat_map(|(account, permissions)| {
+                permissions.iter().map(move |(permission, key)| KeyInfo {
+                    account: account.clone(),
+                    permission: permission.clone(),
+                    algorithm: key.algorithm.clone(),
+                    public_key: key.public_key.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Replaces the key of `account@permission` by `new_key`, encrypted with the same password.
+    /// Returns the previous key, still needed to sign the on-chain permission update
+    pub fn rotate(
+        &mut self,
+        account: &str,
+        permission: &str,
+        password: &str,
+        new_key: &KeyPair,
+    ) -> Result<KeyPair> {
+        let old_key = self.unlock(account, permission, password)?;
+        let key = EncryptedKey::encrypt(account, permission, new_key, password, &self.kdf)?;
+        self.accounts
+            .entry(account.to_string())
+            .or_default()
+            .insert(permission.to_string(), key);
+        Ok(old_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm;
+
+    fn fast_kdf() -> ScryptParams {
+        ScryptParams {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        }
+    }
+
+    fn keystore() -> Keystore {
+        let mut keystore = Keystore::new().with_kdf(fast_kdf());
+        let admin: KeyPair = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".parse().unwrap();
+        let treasury: KeyPair = "3BZ3HWs2nWucCCvLp7FRFv1K7RR3fAjjEQccf9EJrTv4"
+            .parse()
+            .unwrap();
+        keystore
+            .insert("admin", "active", &admin, "password")
+            .unwrap();
+        keystore
+            .insert("treasury", "owner", &treasury, "password")
+            .unwrap();
+        keystore
+    }
+
+    #[test]
+    fn keystore_should_save_load_and_unlock() {
+        let path = std::env::temp_dir().join(format!("iost-keystore-{}.json", std::process::id()));
+        keystore().save(&path).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let keystore = Keystore::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            keystore.list(),
+            vec![
+                KeyInfo {
+                    account: "admin".to_string(),
+                    permission: "active".to_string(),
+                    algorithm: algorithm::ED25519.to_string(),
+                    public_key: "Gcv8c2tH8qZrUYnKdEEdTtASsxivic2834MQW6mgxqto".to_string(),
+                },
+                KeyInfo {
+                    account: "treasury".to_string(),
+                    permission: "owner".to_string(),
+                    algorithm: algorithm::SECP256K1.to_string(),
+                    public_key: "iWgLQj3VTPN4dZnomuJMMCggv22LFw4nAkA6bmrVsmCo".to_string(),
+                },
+            ]
+        );
+
+        let admin = keystore.unlock("admin", "active", "password").unwrap();
+        assert_eq!(
+            admin.public_base58(),
+            "Gcv8c2tH8qZrUYnKdEEdTtASsxivic2834MQW6mgxqto"
+        );
+        let signature = admin.sign(&[1u8; 32]).unwrap();
+        assert!(admin.verify(&[1u8; 32], &signature).is_ok());
+        let treasury = keystore.unlock("treasury", "owner", "password").unwrap();
+        assert_eq!(
+            treasury.secret_base58(),
+            "3BZ3HWs2nWucCCvLp7FRFv1K7RR3fAjjEQccf9EJrTv4"
+        );
+
+        assert_eq!(
+            keystore.unlock("admin", "active", "wrong").err(),
+            Some(WrongPassword)
+        );
+        assert_eq!(
+            keystore.unlock("admin", "owner", "password").err(),
+            Some(KeyNotFound("admin".to_string(), "owner".to_string()))
+        );
+        assert!(matches!(
+            Keystore::load("/nonexistent/keystore.json"),
+            Err(KeystoreIo(_))
+        ));
+    }
+
+    #[test]
+    fn keystore_should_reject_tampered_entries() {
+        let mut keystore = keystore();
+        let admin = keystore.get("admin", "active").unwrap().clone();
+        keystore
+            .accounts
+            .get_mut("treasury")
+            .unwrap()
+            .insert("active".to_string(), admin);
+        // the ciphertext is bound to its account and permission
+        assert_eq!(
+            keystore.unlock("treasury", "active", "password").err(),
+            Some(WrongPassword)
+        );
+
+        let json = keystore.to_json().unwrap().replace(
+            &format!("\"version\": {}", KEYSTORE_VERSION),
+            "\"version\": 2",
+        );
+        assert!(matches!(
+            Keystore::from_json(&json),
+            Err(InvalidKeystore(_))
+        ));
+        assert!(matches!(Keystore::from_json("{}"), Err(InvalidKeystore(_))));
+
+        // scrypt parameters are bounded before deriving the key
+        for (log_n, r, p) in [(40, 8, 1), (21, 1, 1), (4, 64, 2), (20, 16, 1)].iter() {
+            let mut keystore = keystore();
+            let admin = keystore.accounts.get_mut("admin").unwrap();
+            admin.get_mut("active").unwrap().kdf = ScryptParams {
+                log_n: *log_n,
+                r: *r,
+                p: *p,
+            };
+            assert!(matches!(
+                keystore.unlock("admin", "active", "password"),
+                Err(InvalidKeystore(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn keystore_should_rotate_keys() {
+        let mut keystore = keystore();
+        let new_key = KeyPair::new(algorithm::ED25519).unwrap();
+        let admin: KeyPair = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".parse().unwrap();
+
+        assert_eq!(
+            keystore.insert("admin", "active", &new_key, "password"),
+            Err(KeyExists("admin".to_string(), "active".to_string()))
+        );
+        assert_eq!(
+            keystore.rotate("admin", "active", "wrong", &new_key).err(),
+            Some(WrongPassword)
+        );
+        let old_key = keystore
+            .rotate("admin", "active", "password", &new_key)
+            .unwrap();
+        assert!(old_key == admin);
+        assert!(keystore.unlock("admin", "active", "password").unwrap() == new_key);
+        assert_eq!(
+            keystore.get("admin", "active").unwrap().public_key,
+            new_key.public_base58()
+        );
+    }
+
+    #[test]
+    fn keystore_signer_should_sign_for_its_account() {
+        let signer = keystore().signer("treasury", "owner", "password").unwrap();
+        assert_eq!(signer.signer_id(), "treasury@owner");
+        assert_eq!(signer.algorithm(), algorithm::SECP256K1);
+        let signature = signer.sign_digest(&[3u8; 32]).unwrap();
+        assert!(signer.public_key().verify(&[3u8; 32], &signature).is_ok());
+        assert_eq!(
+            keystore().signer("treasury", "owner", "wrong").err(),
+            Some(WrongPassword)
+        );
+    }
+}
@@ -3,11 +3,19 @@
 extern crate alloc;
 
 pub mod algorithm;
+pub mod batch;
 pub mod error;
+pub mod hd;
 pub mod keypair;
+#[cfg(feature = "std")]
+pub mod keystore;
+pub mod mnemonic;
 pub mod public;
+#[cfg(all(feature = "std", unix))]
+pub mod remote;
 pub mod secret;
 pub mod signature;
+pub mod signer;
 
 mod base58;
 mod constant;
@@ -0,0 +1,252 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
+
+use bitcoin_hashes::{hmac, sha256, sha512, Hash as HashTrait, HashEngine};
+use zeroize::Zeroize;
+
+use crate::error::Error::{
+    InvalidEntropyLength, InvalidMnemonicChecksum, InvalidMnemonicLength, InvalidMnemonicWord,
+};
+use crate::{error, Result};
+
+/// BIP-39 english wordlist
+const WORDLIST: &str = include_str!("wordlist/english.txt");
+/// Rounds of PBKDF2 turning a mnemonic into a seed
+const PBKDF2_ROUNDS: u32 = 2048;
+
+fn words() -> impl Iterator<Item = &'static str> {
+    WORDLIST.lines()
+}
+
+fn word_index(word: &str) -> Option<usize> {
+    words().position(|w| w == word)
+}
+
+fn check_word_count(word_count: usize) -> Result<()> {
+    match word_count {
+        12 | 15 | 18 | 21 | 24 => Ok(()),
+        _ => Err(InvalidMnemonicLength(word_count)),
+    }
+}
+
+/// PBKDF2-HMAC-SHA512, limited to the single 64 bytes block needed by BIP-39
+fn pbkdf2_sha512(password: &[u8], salt: &[u8], rounds: u32) -> [u8; 64] {
+    let mut engine = hmac::HmacEngine::<sha512::Hash>::new(password);
+    engine.input(salt);
+    engine.input(&1u32.to_be_bytes());
+    let mut block = hmac::Hmac::<sha512::Hash>::from_engine(engine).into_inner();
+    let mut result = block;
+    for _ in 1..rounds {
+        let mut engine = hmac::HmacEngine::<sha512::Hash>::new(password);
+        engine.input(&block);
+        block = hmac::Hmac::<sha512::Hash>::from_engine(engine).into_inner();
+        for (r, b) in result.iter_mut().zip(block.iter()) {
+            *r ^= b;
+        }
+    }
+    block.zeroize();
+    result
+}
+
+/// A BIP-39 mnemonic phrase of 12, 15, 18, 21 or 24 english words, wiped from memory when dropped
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic {
+    phrase: String,
+}
+
+impl Mnemonic {
+    /// Generate a mnemonic of `word_count` words from fresh entropy
+    #[cfg(feature = "std")]
+    pub fn generate(word_count: usize) -> Result<Self> {
+        use rand::RngCore;
+
+        check_word_count(word_count)?;
+        let mut entropy = [0u8; 32];
+        let entropy_length = word_count / 3 * 4;
+        rand::rngs::OsRng.fill_bytes(&mut entropy[..entropy_length]);
+        let mnemonic = Mnemonic::from_entropy(&entropy[..entropy_length]);
+        entropy.zeroize();
+        mnemonic
+    }
+
+    /// Mnemonic encoding `entropy` of 16, 20, 24, 28 or 32 bytes
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self> {
+        match entropy.len() {
+            16 | 20 | 24 | 28 | 32 => {}
+            len => return Err(InvalidEntropyLength(len)),
+        }
+        // entropy followed by its checksum, one bit per 32 bits of entropy
+        let mut bits = Vec::from(entropy);
+        bits.push(sha256::Hash::hash(entropy)[0]);
+        let word_count = (entropy.len() * 8 + entropy.len() / 4) / 11;
+
+        let wordlist: Vec<&str> = words().collect();
+        let mut phrase = String::new();
+        for i in 0..word_count {
+            let mut index = 0;
+            for bit in i * 11..(i + 1) * 11 {
+                index = (index << 1) | ((bits[bit / 8] >> (7 - bit % 8)) & 1) as usize;
+            }
+            if i > 0 {
+                phrase.push(' ');
+            }
+            phrase.push_str(wordlist[index]);
+        }
+        bits.zeroize();
+        Ok(Mnemonic { phrase })
+    }
+
+    /// Parse a mnemonic, checking its words and checksum
+    pub fn parse(phrase: &str) -> Result<Self> {
+        let mnemonic = Mnemonic {
+            phrase: phrase.split_whitespace().collect::<Vec<_>>().join(" "),
+        };
+        mnemonic.entropy()?;
+        Ok(mnemonic)
+    }
+
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.phrase.split(' ').count()
+    }
+
+    /// Entropy encoded by the mnemonic
+    pub fn entropy(&self) -> Result<Vec<u8>> {
+        let word_count = self.word_count();
+        check_word_count(word_count)?;
+        let entropy_length = word_count / 3 * 4;
+        let checksum_bits = word_count / 3;
+        // entropy followed by the checksum, of at most 8 bits
+        let mut bits = vec![0u8; entropy_length + 1];
+        for (i, word) in self.phrase.split(' ').enumerate() {
+            let index = word_index(word).ok_or_else(|| InvalidMnemonicWord(word.to_string()))?;
+            for j in 0..11 {
+                if index & (1 << (10 - j)) != 0 {
+                    let bit = i * 11 + j;
+                    bits[bit / 8] |= 1 << (7 - bit % 8);
+                }
+            }
+        }
+
+        let checksum = sha256::Hash::hash(&bits[..entropy_length])[0] >> (8 - checksum_bits);
+        if bits[entropy_length] >> (8 - checksum_bits) != checksum {
+            bits.zeroize();
+            return Err(InvalidMnemonicChecksum);
+        }
+        bits.truncate(entropy_length);
+        Ok(bits)
+    }
+
+    /// 64 bytes seed of the mnemonic, protected by an optional `passphrase`. Non-ascii passphrases
+    /// must be NFKD normalized by the caller.
+    pub fn to_seed(&self, passphrase: &str) -> [u8; 64] {
+        let mut salt = String::from("mnemonic");
+        salt.push_str(passphrase);
+        let seed = pbkdf2_sha512(self.phrase.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS);
+        salt.zeroize();
+        seed
+    }
+}
+
+impl Drop for Mnemonic {
+    fn drop(&mut self) {
+        self.phrase.zeroize();
+    }
+}
+
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Mnemonic({} words)", self.word_count())
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Mnemonic::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mnemonic_should_match_bip39_vectors() {
+        // from the reference implementation, with the passphrase "TREZOR"
+        let cases = vec![
+            (
+                "00000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+                "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+            ),
+            (
+                "ffffffffffffffffffffffffffffffff",
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+                "ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13332572917f0f8e5a589620c6f15b11c61dee327651a14c34e18231052e48c069",
+            ),
+            (
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art",
+                "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8",
+            ),
+        ];
+        for (entropy, phrase, seed) in cases {
+            let entropy = hex::decode(entropy).unwrap();
+            let mnemonic = Mnemonic::from_entropy(&entropy).unwrap();
+            assert_eq!(mnemonic.phrase(), phrase);
+            let parsed: Mnemonic = phrase.parse().unwrap();
+            assert_eq!(parsed.entropy().unwrap(), entropy);
+            assert_eq!(hex::encode(&parsed.to_seed("TREZOR")[..]), seed);
+        }
+    }
+
+    #[test]
+    fn mnemonic_should_reject_invalid_phrases() {
+        assert_eq!(
+            Mnemonic::parse("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"),
+            Err(InvalidMnemonicChecksum)
+        );
+        assert_eq!(
+            Mnemonic::parse("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon iost"),
+            Err(InvalidMnemonicWord("iost".to_string()))
+        );
+        assert_eq!(
+            Mnemonic::parse("abandon abandon about"),
+            Err(InvalidMnemonicLength(3))
+        );
+        assert_eq!(
+            Mnemonic::from_entropy(&[0u8; 15]),
+            Err(InvalidEntropyLength(15))
+        );
+        // extra whitespace is ignored
+        assert!(Mnemonic::parse(" zoo zoo zoo zoo zoo zoo  zoo zoo zoo zoo zoo wrong\n").is_ok());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn mnemonic_generate_should_round_trip() {
+        for word_count in [12, 15, 18, 21, 24].iter() {
+            let mnemonic = Mnemonic::generate(*word_count).unwrap();
+            assert_eq!(mnemonic.word_count(), *word_count);
+            assert!(Mnemonic::parse(mnemonic.phrase()).unwrap() == mnemonic);
+        }
+        assert_eq!(
+            Mnemonic::generate(13).err(),
+            Some(InvalidMnemonicLength(13))
+        );
+        let mnemonic = Mnemonic::generate(12).unwrap();
+        assert!(!format!("{:?}", mnemonic).contains(mnemonic.phrase()));
+    }
+}
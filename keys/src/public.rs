@@ -1,183 +1,134 @@
-// use crate::base58;
-// use crate::constant::*;
-// use crate::secret::SecretKey;
-// use crate::signature::Signature;
-// use crate::{error, hash};
-// use alloc::format;
-// use alloc::string::String;
-// use alloc::vec::Vec;
-// use bitcoin_hashes::{sha256, Hash as HashTrait};
-// use core::{fmt, str::FromStr};
-//
-// /// A Secp256k1 public key
-// #[derive(Clone, PartialEq, Eq, Debug)]
-// pub struct PublicKey {
-//     /// Whether this public key should be serialized as compressed
-//     pub compressed: bool,
-//     /// The actual Secp256k1 key
-//     pub key: secp256k1::PublicKey,
-// }
-//
-// impl PublicKey {
-//     /// Serialize the public key to bytes
-//     pub fn to_bytes(&self) -> Vec<u8> {
-//         if self.compressed {
-//             self.key.serialize_compressed().to_vec()
-//         } else {
-//             self.key.serialize().to_vec()
-//         }
-//     }
-//
-//     /// Serialize the public key to Eos format string
-//     pub fn to_eos_fmt(&self) -> String {
-//         let h160 = hash::ripemd160(&self.key.serialize_compressed());
-//         let mut public_key = [0u8; PUBLIC_KEY_WITH_CHECKSUM_SIZE];
-//         public_key[..PUBLIC_KEY_SIZE].copy_from_slice(self.to_bytes().as_ref());
-//         public_key[PUBLIC_KEY_SIZE..].copy_from_slice(&h160.take()[..PUBLIC_KEY_CHECKSUM_SIZE]);
-//
-//         format!("EOS{}", base58::encode_slice(&public_key))
-//     }
-//
-//     /// Verify a signature on a message with public key.
-//     pub fn verify(&self, message_slice: &[u8], signature: &Signature) -> crate::Result<()> {
-//         let msg_hash = sha256::Hash::hash(&message_slice);
-//         self.verify_hash(&msg_hash, &signature)
-//     }
-//
-//     /// Verify a signature on a hash with public key.
-//     pub fn verify_hash(&self, hash: &[u8], signature: &Signature) -> crate::Result<()> {
-//         let msg = secp256k1::Message::parse_slice(&hash)?;
-//         match secp256k1::verify(&msg, &signature.sig, &self.key) {
-//             true => Ok(()),
-//             false => Err(crate::error::Error::VerifyFailed),
-//         }
-//     }
-//
-//     /// Deserialize a public key from a slice
-//     pub fn from_slice(data: &[u8]) -> crate::Result<PublicKey> {
-//         let compressed: bool = match data.len() {
-//             PUBLIC_KEY_SIZE => true,
-//             UNCOMPRESSED_PUBLIC_KEY_SIZE => false,
-//             len => {
-//                 return Err(base58::Error::InvalidLength(len).into());
-//             }
-//         };
-//
-//         Ok(PublicKey {
-//             compressed,
-//             key: secp256k1::PublicKey::parse_slice(
-//                 &data,
-//                 Some(secp256k1::PublicKeyFormat::Compressed),
-//             )?,
-//         })
-//     }
-// }
-//
-// impl fmt::Display for PublicKey {
-//     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-//         if self.compressed {
-//             write!(f, "{}", self.to_eos_fmt())?;
-//         } else {
-//             for ch in &self.key.serialize()[..] {
-//                 write!(f, "{:02x}", ch)?;
-//             }
-//         }
-//
-//         Ok(())
-//     }
-// }
-//
-// impl FromStr for PublicKey {
-//     type Err = error::Error;
-//     fn from_str(s: &str) -> crate::Result<PublicKey> {
-//         if !s.starts_with("EOS") {
-//             return Err(secp256k1::Error::InvalidPublicKey.into());
-//         }
-//
-//         let s_hex = base58::from(&s[3..])?;
-//         let format = match s_hex.len() {
-//             PUBLIC_KEY_WITH_CHECKSUM_SIZE => secp256k1::PublicKeyFormat::Compressed,
-//             _ => secp256k1::PublicKeyFormat::Full,
-//         };
-//         let raw = &s_hex[..PUBLIC_KEY_SIZE];
-//         // TODO verify with checksum
-//         let _checksum = &s_hex[PUBLIC_KEY_SIZE..];
-//         let key = secp256k1::PublicKey::parse_slice(&raw, Some(format))?;
-//
-//         Ok(PublicKey {
-//             key,
-//             compressed: true,
-//         })
-//     }
-// }
-//
-// impl<'a> From<&'a SecretKey> for PublicKey {
-//     /// Derive this public key from its corresponding `SecretKey`.
-//     fn from(sk: &SecretKey) -> PublicKey {
-//         let pk = secp256k1::PublicKey::from_secret_key(&sk.key);
-//
-//         PublicKey {
-//             compressed: true,
-//             key: pk,
-//         }
-//     }
-// }
-//
-// #[cfg(test)]
-// mod test {
-//     use super::PublicKey;
-//     use crate::error;
-//     use crate::signature::Signature;
-//     use alloc::string::ToString;
-//     use core::str::FromStr;
-//     use secp256k1;
-//
-//     #[test]
-//     fn pk_from_str_should_work() {
-//         let pk_str = "EOS8FdQ4gt16pFcSiXAYCcHnkHTS2nNLFWGZXW5sioAdvQuMxKhAm";
-//         let pk = PublicKey::from_str(pk_str);
-//         assert!(pk.is_ok());
-//         assert_eq!(pk.unwrap().to_string(), pk_str);
-//     }
-//
-//     #[test]
-//     fn pk_from_str_should_error() {
-//         let pk_str = "8FdQ4gt16pFcSiXAYCcHnkHTS2nNLFWGZXW5sioAdvQuMxKhAm";
-//         let pk = PublicKey::from_str(pk_str);
-//         assert!(pk.is_err());
-//         assert_eq!(
-//             pk.unwrap_err(),
-//             error::Error::Secp256k1(secp256k1::Error::InvalidPublicKey)
-//         );
-//     }
-//
-//     #[test]
-//     fn pk_verify_should_work() {
-//         let pk_str = "EOS86jwjSu9YkD4JDJ7nGK1Rx2SmvNMQ3XiKrvFndABzLDPwk1ZHx";
-//         let sig_str = "SIG_K1_KomV6FEHKdtZxGDwhwSubEAcJ7VhtUQpEt5P6iDz33ic936aSXx87B2L56C8JLQkqNpp1W8ZXjrKiLHUEB4LCGeXvbtVuR";
-//
-//         let pk = PublicKey::from_str(pk_str);
-//         dbg!(&pk);
-//         assert!(pk.is_ok());
-//         let sig = Signature::from_str(sig_str);
-//         assert!(sig.is_ok());
-//
-//         let vfy = pk.unwrap().verify("hello".as_bytes(), &sig.unwrap());
-//         assert!(vfy.is_ok());
-//     }
-//
-//     #[test]
-//     fn pk_verify_should_error() {
-//         let pk_str = "EOS86jwjSu9YkD4JDJ7nGK1Rx2SmvNMQ3XiKrvFndABzLDPwk1ZHx";
-//         let sig_str = "SIG_K1_KomV6FEHKdtZxGDwhwSubEAcJ7VhtUQpEt5P6iDz33ic936aSXx87B2L56C8JLQkqNpp1W8ZXjrKiLHUEB4LCGeXvbtVuR";
-//
-//         let pk = PublicKey::from_str(pk_str);
-//         assert!(pk.is_ok());
-//         let sig = Signature::from_str(sig_str);
-//         assert!(sig.is_ok());
-//
-//         let vfy = pk.unwrap().verify("world".as_bytes(), &sig.unwrap());
-//         assert!(vfy.is_err());
-//         assert_eq!(vfy, Err(crate::error::Error::VerifyFailed));
-//     }
-// }
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
+
+use crate::algorithm::{Algorithm, AlgorithmEd25519, AlgorithmSecp256k1};
+use crate::error::Error::{ErrorEd25519, InvalidPublicKeyLength};
+use crate::{algorithm, base58, error};
+
+/// An ed25519 or secp256k1 public key
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PublicKey {
+    /// 32 bytes ed25519 public key
+    Ed25519([u8; 32]),
+    /// 33 bytes compressed secp256k1 public key
+    Secp256k1([u8; 33]),
+}
+
+impl PublicKey {
+    /// Parse a public key of `algorithm_name`, checking it is a point of the curve
+    pub fn from_bytes(algorithm_name: &str, bytes: &[u8]) -> error::Result<Self> {
+        if algorithm_name.eq_ignore_ascii_case(algorithm::ED25519) {
+            let key = ed25519_dalek::PublicKey::from_bytes(bytes).map_err(|_| ErrorEd25519)?;
+            Ok(PublicKey::Ed25519(key.to_bytes()))
+        } else if algorithm_name.eq_ignore_ascii_case(algorithm::SECP256K1) {
+            let key = secp256k1::PublicKey::parse_slice(bytes, None)?;
+            Ok(PublicKey::Secp256k1(key.serialize_compressed()))
+        } else {
+            Err(error::Error::UnknownAlgorithm(String::from(algorithm_name)))
+        }
+    }
+
+    /// Parse a public key, telling the algorithm from its length
+    fn from_raw(bytes: &[u8]) -> error::Result<Self> {
+        match bytes.len() {
+            32 => PublicKey::from_bytes(algorithm::ED25519, bytes),
+            33 => PublicKey::from_bytes(algorithm::SECP256K1, bytes),
+            len => Err(InvalidPublicKeyLength(len)),
+        }
+    }
+
+    /// Parse a base64 public key, as found in the signatures of transactions
+    #[cfg(feature = "std")]
+    pub fn from_base64(s: &str) -> error::Result<Self> {
+        let bytes = base64::decode(s).map_err(|_| error::Error::InvalidBase64)?;
+        PublicKey::from_raw(&bytes)
+    }
+
+    /// Name of the algorithm of this key
+    pub fn algorithm(&self) -> &'static str {
+        match self {
+            PublicKey::Ed25519(_) => algorithm::ED25519,
+            PublicKey::Secp256k1(_) => algorithm::SECP256K1,
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            PublicKey::Ed25519(key) => key,
+            PublicKey::Secp256k1(key) => key,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    pub fn to_base58(&self) -> String {
+        base58::encode_slice(self.as_bytes())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn to_base64(&self) -> String {
+        base64::encode(self.as_bytes())
+    }
+
+    /// Verify `signature` of `message` with this key
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> error::Result<()> {
+        match self {
+            PublicKey::Ed25519(key) => AlgorithmEd25519.verify(message, key, signature),
+            PublicKey::Secp256k1(key) => AlgorithmSecp256k1.verify(message, key, signature),
+        }
+    }
+}
+
+impl fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_base58())
+    }
+}
+
+impl fmt::Debug for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PublicKey({}, {})", self.algorithm(), self.to_base58())
+    }
+}
+
+impl FromStr for PublicKey {
+    type Err = error::Error;
+
+    /// Parse a base58 public key, as shown by iwallet
+    fn from_str(s: &str) -> error::Result<Self> {
+        PublicKey::from_raw(&base58::from(s)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn public_key_should_parse_and_display() {
+        let key: PublicKey = "Gcv8c2tH8qZrUYnKdEEdTtASsxivic2834MQW6mgxqto"
+            .parse()
+            .unwrap();
+        assert_eq!(key.algorithm(), algorithm::ED25519);
+        assert_eq!(
+            key.to_string(),
+            "Gcv8c2tH8qZrUYnKdEEdTtASsxivic2834MQW6mgxqto"
+        );
+        assert_eq!(PublicKey::from_base64(&key.to_base64()).unwrap(), key);
+
+        let key: PublicKey = "iWgLQj3VTPN4dZnomuJMMCggv22LFw4nAkA6bmrVsmCo"
+            .parse()
+            .unwrap();
+        assert_eq!(key.algorithm(), algorithm::SECP256K1);
+        assert_eq!(key.as_bytes().len(), 33);
+
+        assert_eq!(
+            "3BZ3HWs2nWucCCvLp7FRFv1K7RR3".parse::<PublicKey>(),
+            Err(InvalidPublicKeyLength(20))
+        );
+        assert!(PublicKey::from_bytes(algorithm::SECP256K1, &[5u8; 33]).is_err());
+    }
+}
@@ -0,0 +1,209 @@
+//! Signer held by another process, reached through a unix socket.
+//!
+//! Each request and response is a line of JSON:
+//! `{"method":"public_key"}` is answered by `{"public_key":"<base58>"}` and
+//! `{"method":"sign","digest":"<base64>"}` by `{"signature":"<base64>"}`. Failures are answered
+//! by `{"error":"<message>"}`.
+
+use std::convert::TryFrom;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error::RemoteSigner as RemoteSignerError;
+use crate::public::PublicKey;
+use crate::signer::Signer;
+use crate::Result;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+enum Request {
+    PublicKey,
+    Sign { digest: String },
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Response {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+fn remote_error<E: ToString>(e: E) -> crate::error::Error {
+    RemoteSignerError(e.to_string())
+}
+
+/// Client of a signer listening on a unix socket
+#[derive(Clone, Debug)]
+pub struct RemoteSigner {
+    path: PathBuf,
+    public_key: PublicKey,
+}
+
+impl RemoteSigner {
+    /// Connects to the signer listening on `path` and fetches its public key
+    pub fn connect<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let response = RemoteSigner::request(&path, &Request::PublicKey)?;
+        let public_key = response
+            .public_key
+            .ok_or_else(|| remote_error("missing public key"))?
+            .parse()?;
+        Ok(RemoteSigner { path, public_key })
+    }
+
+    fn request(path: &Path, request: &Request) -> Result<Response> {
+        let mut stream = UnixStream::connect(path).map_err(remote_error)?;
+        let mut line = serde_json::to_string(request).map_err(remote_error)?;
+        line.push('\n');
+        stream.write_all(line.as_bytes()).map_err(remote_error)?;
+
+        let mut line = String::new();
+        BufReader::new(stream)
+            .read_line(&mut line)
+            .map_err(remote_error)?;
+        let response: Response = serde_json::from_str(&line).map_err(remote_error)?;
+        match response.error {
+            Some(error) => Err(RemoteSignerError(error)),
+            None => Ok(response),
+        }
+    }
+}
+
+impl Signer for RemoteSigner {
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<Vec<u8>> {
+        let request = Request::Sign {
+            digest: base64::encode(digest),
+        };
+        let signature = RemoteSigner::request(&self.path, &request)?
+            .signature
+            .ok_or_else(|| remote_error("missing signature"))?;
+        let signature = base64::decode(signature).map_err(remote_error)?;
+        // a remote signer is not trusted to sign what it was asked to
+        self.public_key.verify(digest, &signature)?;
+        Ok(signature)
+    }
+}
+
+fn respond<S: Signer + ?Sized>(signer: &S, line: &str) -> Response {
+    let result =
+        serde_json::from_str(line)
+            .map_err(remote_error)
+            .and_then(|request| match request {
+                Request::PublicKey => Ok(Response {
+                    public_key: Some(signer.public_key().to_base58()),
+                    ..Response::default()
+                }),
+                Request::Sign { digest } => {
+                    let digest = base64::decode(digest).map_err(remote_error)?;
+                    let digest = <[u8; 32]>::try_from(digest.as_slice())
+                        .map_err(|_| remote_error("digest is not 32 bytes"))?;
+                    Ok(Response {
+                        signature: Some(base64::encode(signer.sign_digest(&digest)?)),
+                        ..Response::default()
+                    })
+                }
+            });
+    result.unwrap_or_else(|e| Response {
+        error: Some(e.to_string()),
+        ..Response::default()
+    })
+}
+
+/// Answers the requests of one connection with `signer`, until the client disconnects
+pub fn serve_connection<S: Signer + ?Sized>(signer: &S, stream: UnixStream) -> Result<()> {
+    let mut writer = stream.try_clone().map_err(remote_error)?;
+    for line in BufReader::new(stream).lines() {
+        let line = line.map_err(remote_error)?;
+        let mut response = serde_json::to_string(&respond(signer, &line)).map_err(remote_error)?;
+        response.push('\n');
+        writer
+            .write_all(response.as_bytes())
+            .map_err(remote_error)?;
+    }
+    Ok(())
+}
+
+/// Serves `signer` on `listener`, one connection at a time. Only returns on listener failures
+pub fn serve<S: Signer + ?Sized>(signer: &S, listener: &UnixListener) -> Result<()> {
+    for stream in listener.incoming() {
+        // a misbehaving client does not stop the signer
+        let _ = serve_connection(signer, stream.map_err(remote_error)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm;
+    use crate::keypair::KeyPair;
+    use std::thread;
+
+    fn socket_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("iost-signer-{}-{}.sock", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn remote_signer_should_sign_through_socket() {
+        let path = socket_path("sign");
+        let listener = UnixListener::bind(&path).unwrap();
+        let key_pair: KeyPair = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".parse().unwrap();
+        let public_key = *key_pair.public();
+        thread::spawn(move || serve(&key_pair, &listener));
+
+        let signer = RemoteSigner::connect(&path).unwrap();
+        assert_eq!(signer.public_key(), public_key);
+        assert_eq!(signer.algorithm(), algorithm::ED25519);
+        let signature = signer.sign_digest(&[7u8; 32]).unwrap();
+        assert!(public_key.verify(&[7u8; 32], &signature).is_ok());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn remote_signer_should_report_failures() {
+        assert!(matches!(
+            RemoteSigner::connect(socket_path("missing")),
+            Err(RemoteSignerError(_))
+        ));
+
+        let key_pair: KeyPair = "3BZ3HWs2nWucCCvLp7FRFv1K7RR3fAjjEQccf9EJrTv4"
+            .parse()
+            .unwrap();
+        let response = respond(&key_pair, r#"{"method":"sign","digest":"AAAA"}"#);
+        assert_eq!(
+            response.error.unwrap(),
+            "Remote signer error digest is not 32 bytes"
+        );
+        assert!(respond(&key_pair, "not json").error.is_some());
+        assert!(respond(&key_pair, r#"{"method":"public_key"}"#)
+            .public_key
+            .is_some());
+
+        // signatures of another key are rejected
+        let path = socket_path("impostor");
+        let listener = UnixListener::bind(&path).unwrap();
+        let signer = RemoteSigner {
+            path: path.clone(),
+            public_key: *KeyPair::new(algorithm::SECP256K1).unwrap().public(),
+        };
+        thread::spawn(move || serve(&key_pair, &listener));
+        assert_eq!(
+            signer.sign_digest(&[7u8; 32]).err(),
+            Some(crate::error::Error::VerifyFailed)
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+}
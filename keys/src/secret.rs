@@ -1,188 +1,237 @@
-// use crate::base58;
-// use crate::error;
-// use crate::network::Network;
-// use crate::network::Network::Mainnet;
-// use crate::signature::Signature;
-// use alloc::string::String;
-// use alloc::vec;
-// use alloc::vec::Vec;
-// use bitcoin_hashes::{sha256, Hash as HashTrait};
-// use core::fmt::{self, Write};
-// use core::str::FromStr;
-// use rand::Rng;
-//
-// /// A Secp256k1 private key
-// #[derive(Clone, PartialEq, Eq)]
-// pub struct SecretKey {
-//     /// Whether this private key should be serialized as compressed
-//     pub compressed: bool,
-//     /// The network on which this key should be used
-//     pub network: Network,
-//     /// The actual Secp256k1 key
-//     pub key: secp256k1::SecretKey,
-// }
-//
-// impl SecretKey {
-//     /// Creates a new random secret key. Requires compilation with the "rand" feature.
-//     pub fn generate<R>(csprng: &mut R) -> Self
-//     where
-//         R: Rng,
-//     {
-//         Self {
-//             compressed: false,
-//             network: Mainnet,
-//             key: secp256k1::SecretKey::random(csprng),
-//         }
-//     }
-//
-//     /// Serialize the private key to bytes
-//     pub fn to_bytes(&self) -> Vec<u8> {
-//         self.key.serialize().to_vec()
-//     }
-//
-//     /// Format the private key to WIF format.
-//     pub fn fmt_wif(&self, fmt: &mut dyn fmt::Write) -> fmt::Result {
-//         let mut ret = [0; 34];
-//         ret[0] = match self.network {
-//             Network::Mainnet => 128,
-//             Network::Testnet => 239,
-//         };
-//         ret[1..33].copy_from_slice(&self.key.serialize());
-//         let privkey = if self.compressed {
-//             ret[33] = 1;
-//             base58::check_encode_slice(&ret[..])
-//         } else {
-//             base58::check_encode_slice(&ret[..33])
-//         };
-//
-//         fmt.write_str(&privkey)
-//     }
-//
-//     /// Get WIF encoding of this private key.
-//     pub fn to_wif(&self) -> Result<String, core::fmt::Error> {
-//         let mut buf = String::new();
-//         buf.write_fmt(format_args!("{}", self))?;
-//         buf.shrink_to_fit();
-//
-//         Ok(buf)
-//     }
-//
-//     /// Parse WIF encoded private key.
-//     pub fn from_wif(wif: &str) -> crate::Result<SecretKey> {
-//         let data = base58::from_check(wif)?;
-//
-//         let compressed = match data.len() {
-//             33 => false,
-//             34 => true,
-//             _ => {
-//                 return Err(base58::Error::InvalidLength(data.len()).into());
-//             }
-//         };
-//
-//         let network = match data[0] {
-//             128 => Network::Mainnet,
-//             239 => Network::Testnet,
-//             x => {
-//                 return Err(base58::Error::InvalidVersion(vec![x]).into());
-//             }
-//         };
-//
-//         Ok(SecretKey {
-//             compressed,
-//             network,
-//             key: secp256k1::SecretKey::parse_slice(&data[1..33])?,
-//         })
-//     }
-//
-//     /// Deserialize a secret key from a slice
-//     pub fn from_slice(data: &[u8]) -> crate::Result<SecretKey> {
-//         let compressed: bool = match data.len() {
-//             33 => true,
-//             65 => false,
-//             len => {
-//                 return Err(base58::Error::InvalidLength(len).into());
-//             }
-//         };
-//
-//         Ok(SecretKey {
-//             compressed,
-//             network: Mainnet,
-//             key: secp256k1::SecretKey::parse_slice(data)?,
-//         })
-//     }
-//
-//     /// Sign a message with secret key
-//     pub fn sign(&self, message_slice: &[u8]) -> crate::Result<Signature> {
-//         let msg_hash = sha256::Hash::hash(&message_slice);
-//         self.sign_hash(&msg_hash)
-//     }
-//
-//     /// Sign a hash with secret key
-//     pub fn sign_hash(&self, hash: &[u8]) -> crate::Result<Signature> {
-//         let msg = secp256k1::Message::parse_slice(&hash)?;
-//         let (sig, recv_id) = secp256k1::sign(&msg, &self.key);
-//
-//         Ok(Signature { recv_id, sig })
-//     }
-// }
-//
-// impl fmt::Display for SecretKey {
-//     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-//         self.fmt_wif(f)
-//     }
-// }
-//
-// impl fmt::Debug for SecretKey {
-//     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-//         write!(f, "[private key data]")
-//     }
-// }
-//
-// impl FromStr for SecretKey {
-//     type Err = error::Error;
-//     fn from_str(s: &str) -> crate::Result<SecretKey> {
-//         SecretKey::from_wif(s)
-//     }
-// }
-//
-// #[cfg(test)]
-// mod test {
-//     use super::SecretKey;
-//     use crate::public::PublicKey;
-//     use alloc::string::ToString;
-//     #[cfg(feature = "std")]
-//     use rand::thread_rng;
-//
-//     #[cfg(feature = "std")]
-//     #[test]
-//     fn sk_generate_should_work() {
-//         let mut rng = thread_rng();
-//         let _sk = SecretKey::generate(&mut rng);
-//     }
-//
-//     #[test]
-//     fn sk_from_wif_should_work() {
-//         let wif = "5HrBLKfeEdqH9KLMv1daHLVjrXV3DGVERAkN5cdSSc58bzqqfT4";
-//         let sk = SecretKey::from_wif(wif);
-//         assert!(sk.is_ok());
-//     }
-//
-//     #[test]
-//     fn sk_sign_should_work() {
-//         let sk = SecretKey::from_wif("5KJVA9P4xsiRC3zPy1KPa3GA6ffvmyZSxhKPbE924YJphvSCG4F");
-//         assert!(sk.is_ok());
-//         let sk = sk.unwrap();
-//         let pk = PublicKey::from(&sk);
-//         assert_eq!(
-//             pk.to_string(),
-//             "EOS55KuLPN3u9qii2hEhJhkdQSdaVLVPTHdwdkEhszhhCWDthQtfi"
-//         );
-//         let sig = sk.sign("hello".as_bytes());
-//         assert!(sig.is_ok());
-//         let sig = sig.unwrap();
-//         assert!(sig.is_canonical());
-//         // "SIG_K1_K6rYC3ug7nZnxcTRoeqKWHD1u8ySNhSs9bHcW1dgpc5jhSoy4hpfwZAVbzbG3MsW8fdHtxMdvAM3RQpmYjth21YY352U8y"
-//         // assert_eq!(sig.to_string(), "SIG_K1_KL1utX4aFNsSfEWVGtqcLAyhV5juPxhGNB7vGTUGKiWeEgxeXhxpuAPg44t7LeaBQPr5qf3Md7VZtEDZHtsfAwr95rpjGa");
-//         assert_eq!(sig.to_string(), "SIG_K1_K6rYC3ug7nZnxcTRoeqKWHD1u8ySNhSs9bHcW1dgpc5jhSoy4hpfwZAVbzbG3MsW8fdHtxMdvAM3RQpmYjth21YY352U8y");
-//     }
-// }
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
+
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+use crate::algorithm::{Algorithm, AlgorithmEd25519, AlgorithmSecp256k1};
+use crate::error::Error::{ErrorEd25519, InvalidSecretKeyLength, UnknownAlgorithm};
+use crate::public::PublicKey;
+use crate::{algorithm, base58, error};
+
+/// An ed25519 or secp256k1 secret key, wiped from memory when dropped. It is only built by
+/// `from_bytes` and the parsers on top of it, so it always holds a valid key.
+#[derive(Clone)]
+pub struct SecretKey(Key);
+
+#[derive(Clone)]
+enum Key {
+    /// 64 bytes ed25519 secret key, the secret half followed by the public half as iwallet does
+    Ed25519([u8; 64]),
+    /// 32 bytes secp256k1 secret key
+    Secp256k1([u8; 32]),
+}
+
+impl SecretKey {
+    /// Generate a secret key for `algorithm_name`
+    #[cfg(feature = "std")]
+    pub fn generate(algorithm_name: &str) -> error::Result<Self> {
+        let mut sk = algorithm::new(algorithm_name)?.gen_sec_key();
+        let key = SecretKey::from_bytes(algorithm_name, &sk);
+        sk.zeroize();
+        key
+    }
+
+    /// Parse a secret key of `algorithm_name`. For ed25519, the 32 bytes seed is accepted as well
+    /// as the 64 bytes secret key of iwallet.
+    pub fn from_bytes(algorithm_name: &str, bytes: &[u8]) -> error::Result<Self> {
+        if algorithm_name.eq_ignore_ascii_case(algorithm::ED25519) {
+            let mut key = [0u8; 64];
+            match bytes.len() {
+                32 => {
+                    let secret =
+                        ed25519_dalek::SecretKey::from_bytes(bytes).map_err(|_| ErrorEd25519)?;
+                    key[..32].copy_from_slice(bytes);
+                    key[32..].copy_from_slice(ed25519_dalek::PublicKey::from(&secret).as_bytes());
+                }
+                64 => key.copy_from_slice(bytes),
+                len => return Err(InvalidSecretKeyLength(len)),
+            }
+            let key = SecretKey(Key::Ed25519(key));
+            AlgorithmEd25519.check(key.as_bytes())?;
+            Ok(key)
+        } else if algorithm_name.eq_ignore_ascii_case(algorithm::SECP256K1) {
+            AlgorithmSecp256k1.check(bytes)?;
+            let mut key = [0u8; 32];
+            key.copy_from_slice(bytes);
+            Ok(SecretKey(Key::Secp256k1(key)))
+        } else {
+            Err(UnknownAlgorithm(String::from(algorithm_name)))
+        }
+    }
+
+    /// Parse a base58 secret key of `algorithm_name`, as exported by iwallet
+    pub fn from_base58(algorithm_name: &str, s: &str) -> error::Result<Self> {
+        let mut bytes = base58::from(s)?;
+        let key = SecretKey::from_bytes(algorithm_name, &bytes);
+        bytes.zeroize();
+        key
+    }
+
+    /// Parse a base64 secret key of `algorithm_name`
+    #[cfg(feature = "std")]
+    pub fn from_base64(algorithm_name: &str, s: &str) -> error::Result<Self> {
+        let mut bytes = base64::decode(s).map_err(|_| error::Error::InvalidBase64)?;
+        let key = SecretKey::from_bytes(algorithm_name, &bytes);
+        bytes.zeroize();
+        key
+    }
+
+    /// Name of the algorithm of this key
+    pub fn algorithm(&self) -> &'static str {
+        match &self.0 {
+            Key::Ed25519(_) => algorithm::ED25519,
+            Key::Secp256k1(_) => algorithm::SECP256K1,
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        match &self.0 {
+            Key::Ed25519(key) => key,
+            Key::Secp256k1(key) => key,
+        }
+    }
+
+    pub fn to_base58(&self) -> String {
+        base58::encode_slice(self.as_bytes())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn to_base64(&self) -> String {
+        base64::encode(self.as_bytes())
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        match &self.0 {
+            Key::Ed25519(key) => {
+                let mut public = [0u8; 32];
+                public.copy_from_slice(&key[32..]);
+                PublicKey::Ed25519(public)
+            }
+            Key::Secp256k1(key) => {
+                // the key was checked when parsed
+                let secret = secp256k1::SecretKey::parse(key).expect("valid secp256k1 key");
+                PublicKey::Secp256k1(
+                    secp256k1::PublicKey::from_secret_key(&secret).serialize_compressed(),
+                )
+            }
+        }
+    }
+
+    /// Sign `message` with this key. secp256k1 only signs 32 bytes digests
+    pub fn sign(&self, message: &[u8]) -> error::Result<Vec<u8>> {
+        match &self.0 {
+            Key::Ed25519(key) => AlgorithmEd25519.sign(message, key),
+            Key::Secp256k1(key) => AlgorithmSecp256k1.sign(message, key),
+        }
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        match &mut self.0 {
+            Key::Ed25519(key) => key.zeroize(),
+            Key::Secp256k1(key) => key.zeroize(),
+        }
+    }
+}
+
+impl PartialEq for SecretKey {
+    /// Constant-time comparison of the keys
+    fn eq(&self, other: &Self) -> bool {
+        self.algorithm() == other.algorithm() && bool::from(self.as_bytes().ct_eq(other.as_bytes()))
+    }
+}
+
+impl Eq for SecretKey {}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretKey({}, ..)", self.algorithm())
+    }
+}
+
+impl FromStr for SecretKey {
+    type Err = error::Error;
+
+    /// Parse a base58 secret key, telling the algorithm from its length: 64 bytes keys are
+    /// ed25519 and 32 bytes keys secp256k1
+    fn from_str(s: &str) -> error::Result<Self> {
+        let mut bytes = base58::from(s)?;
+        let key = match bytes.len() {
+            64 => SecretKey::from_bytes(algorithm::ED25519, &bytes),
+            32 => SecretKey::from_bytes(algorithm::SECP256K1, &bytes),
+            len => Err(InvalidSecretKeyLength(len)),
+        };
+        bytes.zeroize();
+        key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn secret_key_should_parse_and_sign() {
+        let key: SecretKey = "2yquS3ySrGWPEKywCPzX4RTJugqRh7kJSo5aehsLYPEWkUxBWA39oMrZ7ZxuM4fgyXYs2cPwh5n8aNNpH5x2VyK1".parse().unwrap();
+        assert_eq!(key.algorithm(), algorithm::ED25519);
+        assert_eq!(
+            key.public_key().to_string(),
+            "Gcv8c2tH8qZrUYnKdEEdTtASsxivic2834MQW6mgxqto"
+        );
+        assert_eq!(
+            SecretKey::from_base64(algorithm::ED25519, &key.to_base64()).unwrap(),
+            key
+        );
+        let signature = key.sign(b"message").unwrap();
+        assert!(key.public_key().verify(b"message", &signature).is_ok());
+        assert!(key.public_key().verify(b"other", &signature).is_err());
+
+        let key: SecretKey = "3BZ3HWs2nWucCCvLp7FRFv1K7RR3fAjjEQccf9EJrTv4"
+            .parse()
+            .unwrap();
+        assert_eq!(key.algorithm(), algorithm::SECP256K1);
+        assert_eq!(
+            key.public_key().to_string(),
+            "iWgLQj3VTPN4dZnomuJMMCggv22LFw4nAkA6bmrVsmCo"
+        );
+        assert_eq!(
+            key.to_base58(),
+            "3BZ3HWs2nWucCCvLp7FRFv1K7RR3fAjjEQccf9EJrTv4"
+        );
+        let signature = key.sign(&[3u8; 32]).unwrap();
+        assert!(key.public_key().verify(&[3u8; 32], &signature).is_ok());
+        assert!(key.sign(b"not a digest").is_err());
+
+        assert_ne!(
+            SecretKey::from_bytes(algorithm::SECP256K1, &[1u8; 32]).unwrap(),
+            SecretKey::from_bytes(algorithm::SECP256K1, &[2u8; 32]).unwrap()
+        );
+        assert_ne!(
+            SecretKey::from_bytes(algorithm::ED25519, &[1u8; 32]).unwrap(),
+            SecretKey::from_bytes(algorithm::ED25519, &[2u8; 32]).unwrap()
+        );
+        assert_eq!(
+            "3BZ3HWs2nWucCCvLp7FRFv1K7RR3".parse::<SecretKey>(),
+            Err(InvalidSecretKeyLength(20))
+        );
+        assert!(!format!("{:?}", key).contains("3BZ3"));
+    }
+
+    #[test]
+    fn secret_key_should_reject_invalid_bytes() {
+        assert_eq!(
+            SecretKey::from_bytes(algorithm::SECP256K1, &[0u8; 32]),
+            Err(error::Error::InvalidSecretKey)
+        );
+        let mut key = [1u8; 64];
+        key[32..].copy_from_slice(&[2u8; 32]);
+        assert_eq!(
+            SecretKey::from_bytes(algorithm::ED25519, &key),
+            Err(error::Error::PublicKeyMismatch)
+        );
+    }
+}
@@ -1,122 +1,169 @@
-// use crate::{base58, error, hash};
-// use byteorder::{ByteOrder, LittleEndian};
-// use core::fmt;
-// use core::str::FromStr;
-//
-// /// An secp256k1 signature.
-// #[derive(Clone, PartialEq, Eq, Debug)]
-// pub struct Signature {
-//     pub recv_id: secp256k1::RecoveryId,
-//     pub sig: secp256k1::Signature,
-// }
-//
-// impl Signature {
-//     pub fn is_canonical(&self) -> bool {
-//         self.sig.is_canonical()
-//     }
-//
-//     pub fn serialize_compact(&self) -> [u8; 65] {
-//         let mut data: [u8; 65] = [0u8; 65];
-//         data[0] = self.recv_id.serialize() + 27 + 4;
-//         data[1..65].copy_from_slice(&self.sig.serialize());
-//         data
-//     }
-//
-//     pub fn from_compact(data: &[u8; 65]) -> crate::Result<Self> {
-//         let recv_id = if data[0] >= 31 { data[0] - 4 } else { data[0] };
-//         let recv_id = secp256k1::RecoveryId::parse_rpc(recv_id)?;
-//         let sig = secp256k1::Signature::parse_slice(&data[1..])?;
-//         Ok(Self { recv_id, sig })
-//     }
-// }
-//
-// impl FromStr for Signature {
-//     type Err = error::Error;
-//
-//     fn from_str(s: &str) -> crate::Result<Signature> {
-//         if !s.starts_with("SIG_K1_") {
-//             return Err(secp256k1::Error::InvalidSignature.into());
-//         }
-//
-//         let s_hex = base58::from(&s[7..])?;
-//         // recovery id length: 1
-//         // signature length: 64
-//         // checksum length: 4
-//         if s_hex.len() != 1 + 64 + 4 {
-//             return Err(secp256k1::Error::InvalidSignature.into());
-//         }
-//         let mut recv_id = s_hex[0];
-//         if recv_id >= 4 + 27 {
-//             recv_id = recv_id - 4 - 27
-//         }
-//         let recv_id =
-//             secp256k1::RecoveryId::parse(recv_id).map_err(|err| error::Error::Secp256k1(err))?;
-//         let data = &s_hex[1..65];
-//
-//         // Verify checksum
-//         let mut checksum_data = [0u8; 67];
-//         checksum_data[..65].copy_from_slice(&s_hex[..65]);
-//         checksum_data[65..67].copy_from_slice(b"K1");
-//         let expected = LittleEndian::read_u32(&hash::ripemd160(&checksum_data)[..4]);
-//         let actual = LittleEndian::read_u32(&s_hex[65..69]);
-//         if expected != actual {
-//             return Err(base58::Error::BadChecksum(expected, actual).into());
-//         }
-//
-//         let sig =
-//             secp256k1::Signature::parse_slice(data).map_err(|err| error::Error::Secp256k1(err))?;
-//
-//         Ok(Signature { recv_id, sig })
-//     }
-// }
-//
-// impl fmt::Display for Signature {
-//     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-//         let recv_id = self.recv_id;
-//         let sig = self.sig.clone();
-//
-//         // See https://github.com/EOSIO/fc/blob/f4755d330faf9d2342d646a93f9a27bf68ca759e/src/crypto/elliptic_impl_priv.cpp
-//         let mut checksum_data: [u8; 67] = [0u8; 67];
-//         let recovery_id_i32: u8 = recv_id.into();
-//         checksum_data[0] = recovery_id_i32 + 4 + 27;
-//         checksum_data[1..65].copy_from_slice(&sig.serialize());
-//         checksum_data[65..].copy_from_slice(b"K1");
-//
-//         // Compute ripemd160 checksum
-//         let checksum_h160 = hash::ripemd160(&checksum_data);
-//         let checksum = &checksum_h160.take()[..4];
-//
-//         // Signature slice
-//         let mut sig_slice: [u8; 69] = [0u8; 69];
-//         sig_slice[..65].copy_from_slice(&checksum_data[..65]);
-//         sig_slice[65..].copy_from_slice(&checksum[..]);
-//
-//         write!(f, "SIG_K1_{}", base58::encode_slice(&sig_slice))?;
-//
-//         Ok(())
-//     }
-// }
-//
-// #[cfg(test)]
-// mod test {
-//     use super::Signature;
-//     use alloc::string::ToString;
-//     use core::str::FromStr;
-//
-//     #[test]
-//     fn sig_from_str_should_work() {
-//         let sig_str = "SIG_K1_KBJgSuRYtHZcrWThugi4ygFabto756zuQQo8XeEpyRtBXLb9kbJtNW3xDcS14Rc14E8iHqLrdx46nenG5T7R4426Bspyzk";
-//         let sig = Signature::from_str(sig_str);
-//         assert!(sig.is_ok());
-//         let sig = sig.unwrap();
-//         assert!(sig.is_canonical());
-//         assert_eq!(sig.to_string(), sig_str);
-//     }
-//
-//     #[test]
-//     fn sig_from_str_should_error() {
-//         let sig_str = "KomV6FEHKdtZxGDwhwSubEAcJ7VhtUQpEt5P6iDz33ic936aSXx87B2L56C8JLQkqNpp1W8ZXjrKiLHUEB4LCGeXvbtVuR";
-//         let sig = Signature::from_str(sig_str);
-//         assert!(sig.is_err());
-//     }
-// }
+use crate::error::Error::{InvalidSignatureLength, NonCanonicalSignature};
+use crate::public::PublicKey;
+use crate::Result;
+
+/// Length of a recoverable signature: the header byte followed by the compact signature
+pub const RECOVERABLE_SIGNATURE_LENGTH: usize = 65;
+
+/// A recoverable secp256k1 signature, from which the public key of the signer can be recovered
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Signature {
+    pub recv_id: secp256k1::RecoveryId,
+    pub sig: secp256k1::Signature,
+}
+
+impl Signature {
+    /// Deterministic (RFC 6979) low-S signature of the 32 bytes `message`
+    pub fn sign(message: &[u8], sec_key: &[u8]) -> Result<Self> {
+        let msg = secp256k1::Message::parse_slice(message)?;
+        let secret_key = secp256k1::SecretKey::parse_slice(sec_key)?;
+        let (sig, recv_id) = secp256k1::sign(&msg, &secret_key);
+        let signature = Signature { recv_id, sig };
+        if !signature.is_canonical() {
+            return Err(NonCanonicalSignature);
+        }
+        Ok(signature)
+    }
+
+    /// Whether S is in the lower half of the curve order, the only form accepted by `verify`
+    pub fn is_canonical(&self) -> bool {
+        !self.sig.s.is_high()
+    }
+
+    /// 65 bytes encoding: `31 + recovery id`, then R and S
+    pub fn serialize_compact(&self) -> [u8; RECOVERABLE_SIGNATURE_LENGTH] {
+        let mut data = [0u8; RECOVERABLE_SIGNATURE_LENGTH];
+        data[0] = self.recv_id.serialize() + 27 + 4;
+        data[1..].copy_from_slice(&self.sig.serialize());
+        data
+    }
+
+    /// Parse a 65 bytes signature, with a header of `27 + recovery id`, `31 + recovery id` or the
+    /// bare recovery id. High-S signatures are rejected.
+    pub fn from_compact(data: &[u8]) -> Result<Self> {
+        if data.len() != RECOVERABLE_SIGNATURE_LENGTH {
+            return Err(InvalidSignatureLength(data.len()));
+        }
+        let recv_id = match data[0] {
+            header @ 31..=34 => header - 31,
+            header @ 27..=30 => header - 27,
+            header => header,
+        };
+        let signature = Signature {
+            recv_id: secp256k1::RecoveryId::parse(recv_id)?,
+            sig: secp256k1::Signature::parse_slice(&data[1..])?,
+        };
+        if !signature.is_canonical() {
+            return Err(NonCanonicalSignature);
+        }
+        Ok(signature)
+    }
+
+    /// Public key of the signer of the 32 bytes `message`
+    pub fn recover_pub_key(&self, message: &[u8]) -> Result<PublicKey> {
+        let msg = secp256k1::Message::parse_slice(message)?;
+        let public_key = secp256k1::recover(&msg, &self.sig, &self.recv_id)?;
+        Ok(PublicKey::Secp256k1(public_key.serialize_compressed()))
+    }
+}
+
+/// Public key of the signer of `message`, from its 65 bytes recoverable `signature`
+pub fn recover_pub_key(message: &[u8], signature: &[u8]) -> Result<PublicKey> {
+    Signature::from_compact(signature)?.recover_pub_key(message)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::{Algorithm, AlgorithmSecp256k1};
+    use crate::keypair::KeyPair;
+
+    fn key_pair() -> KeyPair {
+        "xjggJ3TrLXz7qEwrGG3Rc4Fz59imjixhXpViq9W7Ncx"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn signature_should_recover_pub_key() {
+        let key_pair = key_pair();
+        for i in 0..32u8 {
+            let message = [i; 32];
+            let signature = Signature::sign(&message, key_pair.secret().as_bytes()).unwrap();
+            assert!(signature.is_canonical());
+            let compact = signature.serialize_compact();
+            assert!(compact[0] >= 31 && compact[0] <= 34);
+            assert_eq!(Signature::from_compact(&compact).unwrap(), signature);
+            assert_eq!(
+                recover_pub_key(&message, &compact).unwrap(),
+                *key_pair.public()
+            );
+            // the compact part is the plain signature
+            assert!(key_pair.verify(&message, &compact[1..]).is_ok());
+            // deterministic
+            assert_eq!(
+                Signature::sign(&message, key_pair.secret().as_bytes()).unwrap(),
+                signature
+            );
+        }
+
+        let signature = AlgorithmSecp256k1
+            .sign_recoverable(&[1u8; 32], key_pair.secret().as_bytes())
+            .unwrap();
+        assert_eq!(
+            AlgorithmSecp256k1
+                .recover_pub_key(&[1u8; 32], &signature.serialize_compact())
+                .unwrap(),
+            *key_pair.public()
+        );
+        let mut compact = signature.serialize_compact();
+        compact[0] -= 4;
+        assert_eq!(
+            recover_pub_key(&[1u8; 32], &compact).unwrap(),
+            *key_pair.public()
+        );
+        assert_ne!(
+            recover_pub_key(&[2u8; 32], &compact).unwrap(),
+            *key_pair.public()
+        );
+    }
+
+    #[test]
+    fn signature_should_reject_high_s_and_malformed() {
+        let key_pair = key_pair();
+        let message = [9u8; 32];
+        let signature = Signature::sign(&message, key_pair.secret().as_bytes()).unwrap();
+
+        // (R, n - S) also verifies on the curve, but is the malleated form
+        let mut high = signature.clone();
+        high.sig.s = -high.sig.s.clone();
+        assert!(!high.is_canonical());
+        assert_eq!(
+            Signature::from_compact(&high.serialize_compact()),
+            Err(NonCanonicalSignature)
+        );
+        assert_eq!(
+            AlgorithmSecp256k1.verify(
+                &message,
+                key_pair.public().as_bytes(),
+                &high.sig.serialize()
+            ),
+            Err(NonCanonicalSignature)
+        );
+        assert!(AlgorithmSecp256k1
+            .verify(
+                &message,
+                key_pair.public().as_bytes(),
+                &signature.sig.serialize()
+            )
+            .is_ok());
+
+        assert_eq!(
+            Signature::from_compact(&[31u8; 64]),
+            Err(InvalidSignatureLength(64))
+        );
+        let mut compact = signature.serialize_compact();
+        compact[0] = 35;
+        assert!(Signature::from_compact(&compact).is_err());
+    }
+}
@@ -0,0 +1,47 @@
+use alloc::vec::Vec;
+
+use crate::keypair::KeyPair;
+use crate::public::PublicKey;
+use crate::Result;
+
+/// Signs digests with a key it does not need to expose, such as a key held by another process or
+/// by a hardware module
+pub trait Signer {
+    fn public_key(&self) -> PublicKey;
+
+    /// Name of the algorithm of the key, `ED25519` or `SECP256K1`
+    fn algorithm(&self) -> &'static str {
+        self.public_key().algorithm()
+    }
+
+    /// Signs the SHA3-256 `digest` of a transaction
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<Vec<u8>>;
+}
+
+impl Signer for KeyPair {
+    fn public_key(&self) -> PublicKey {
+        *self.public()
+    }
+
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<Vec<u8>> {
+        self.sign(digest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm;
+
+    #[test]
+    fn key_pair_should_sign_as_signer() {
+        let key_pair: KeyPair = "3BZ3HWs2nWucCCvLp7FRFv1K7RR3fAjjEQccf9EJrTv4"
+            .parse()
+            .unwrap();
+        let signer: &dyn Signer = &key_pair;
+        assert_eq!(signer.algorithm(), algorithm::SECP256K1);
+        assert_eq!(signer.public_key(), *key_pair.public());
+        let signature = signer.sign_digest(&[5u8; 32]).unwrap();
+        assert!(signer.public_key().verify(&[5u8; 32], &signature).is_ok());
+    }
+}